@@ -0,0 +1,70 @@
+//! Measures the cost of dispatching on `InputEvent::kind` compared to matching on
+//! `InputEvent::event_type` directly.
+//!
+//! Both should compile to a single jump over the event type, so they are expected to perform about
+//! the same.
+//! Run with `cargo bench --bench kind`.
+
+use std::{hint::black_box, time::Instant};
+
+use evdevil::event::{
+    Abs, AbsEvent, EventKind, EventType, InputEvent, Key, KeyEvent, KeyState, Led, LedEvent, Misc,
+    MiscEvent, Rel, RelEvent, Syn, SynEvent,
+};
+
+const ROUNDS: usize = 100_000;
+
+fn main() {
+    // A mix of event types, roughly like the stream of a mouse or gamepad.
+    let events: Vec<InputEvent> = [
+        KeyEvent::new(Key::BTN_LEFT, KeyState::PRESSED).into(),
+        RelEvent::new(Rel::X, 3).into(),
+        RelEvent::new(Rel::Y, -2).into(),
+        SynEvent::new(Syn::REPORT).into(),
+        AbsEvent::new(Abs::X, 100).into(),
+        MiscEvent::new(Misc::SCAN, 0x90001).into(),
+        LedEvent::new(Led::CAPSL, true).into(),
+        InputEvent::new(EventType::PWR, 0, 0),
+    ]
+    .into_iter()
+    .cycle()
+    .take(256)
+    .collect();
+
+    let kind = bench(&events, |ev| match ev.kind() {
+        EventKind::Syn(_) => 1,
+        EventKind::Key(_) => 2,
+        EventKind::Rel(_) => 3,
+        EventKind::Abs(_) => 4,
+        EventKind::Misc(_) => 5,
+        EventKind::Led(_) => 6,
+        _ => 0,
+    });
+    let event_type = bench(&events, |ev| match ev.event_type() {
+        EventType::SYN => 1,
+        EventType::KEY => 2,
+        EventType::REL => 3,
+        EventType::ABS => 4,
+        EventType::MSC => 5,
+        EventType::LED => 6,
+        _ => 0,
+    });
+
+    println!("InputEvent::kind:        {kind:.2} ns per event");
+    println!("InputEvent::event_type:  {event_type:.2} ns per event");
+}
+
+/// Returns the average time in nanoseconds `classify` takes per event.
+///
+/// Dispatch only takes around a nanosecond, which a `Duration` would round away.
+fn bench(events: &[InputEvent], classify: impl Fn(&InputEvent) -> u32) -> f64 {
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        let mut sum = 0u32;
+        for ev in black_box(events) {
+            sum = sum.wrapping_add(classify(ev));
+        }
+        black_box(sum);
+    }
+    start.elapsed().as_secs_f64() * 1e9 / (ROUNDS * events.len()) as f64
+}
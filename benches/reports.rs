@@ -0,0 +1,96 @@
+//! Compares the per-report cost of `Evdev::with_reports` and `EventReader::reports`.
+//!
+//! Creates a uinput device, so this needs permission to access `/dev/uinput`.
+//! Run with `cargo bench --bench reports`.
+
+use std::{
+    fs,
+    hint::black_box,
+    io,
+    time::{Duration, Instant},
+};
+
+use evdevil::{
+    Evdev,
+    event::{InputEvent, Rel, RelEvent},
+    uinput::UinputDevice,
+};
+
+/// Number of reports written before reading them back.
+///
+/// This is small enough for the reports to fit in the kernel's event buffer.
+const BATCH: usize = 8;
+
+const ROUNDS: usize = 20_000;
+
+fn main() -> io::Result<()> {
+    if !fs::exists("/dev/uinput")? {
+        eprintln!("`/dev/uinput` doesn't exist, skipping benchmark");
+        return Ok(());
+    }
+
+    let uinput = UinputDevice::builder()?
+        .with_rel_axes([Rel::X, Rel::Y])?
+        .build("evdevil report benchmark")?;
+    let evdev = open_evdev(&uinput)?;
+
+    let with_reports = bench(&uinput, || {
+        let mut count = 0;
+        while count < BATCH {
+            count += evdev.with_reports(|report| {
+                black_box(report);
+            })?;
+        }
+        Ok(())
+    })?;
+
+    let mut reader = evdev.into_reader()?;
+    let reports = bench(&uinput, || {
+        for report in reader.reports().take(BATCH) {
+            black_box(report?);
+        }
+        Ok(())
+    })?;
+
+    println!("Evdev::with_reports:   {with_reports:?} per report");
+    println!("EventReader::reports:  {reports:?} per report");
+    Ok(())
+}
+
+/// Opens the evdev node belonging to `uinput`.
+fn open_evdev(uinput: &UinputDevice) -> io::Result<Evdev> {
+    let dir = format!(
+        "/sys/devices/virtual/input/{}",
+        uinput.sysname()?.to_string_lossy()
+    );
+    for entry in fs::read_dir(dir)? {
+        let name = entry?.file_name();
+        if name.as_encoded_bytes().starts_with(b"event") {
+            let path = format!("/dev/input/{}", name.to_string_lossy());
+            return Evdev::open_with_retry(path, 100, Duration::from_millis(10));
+        }
+    }
+    Err(io::Error::new(
+        io::ErrorKind::NotFound,
+        "no evdev node found for uinput device",
+    ))
+}
+
+/// Writes batches of reports to `uinput`, and returns the average time `read` takes per report
+/// to read them back.
+fn bench(uinput: &UinputDevice, mut read: impl FnMut() -> io::Result<()>) -> io::Result<Duration> {
+    let events: [InputEvent; 2] = [
+        RelEvent::new(Rel::X, 1).into(),
+        RelEvent::new(Rel::Y, 1).into(),
+    ];
+    let mut total = Duration::ZERO;
+    for _ in 0..ROUNDS {
+        for _ in 0..BATCH {
+            uinput.write_events(&events)?;
+        }
+        let start = Instant::now();
+        read()?;
+        total += start.elapsed();
+    }
+    Ok(total / (ROUNDS * BATCH) as u32)
+}
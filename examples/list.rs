@@ -24,10 +24,11 @@ fn run() -> io::Result<()> {
     for res in evdevil::enumerate()? {
         let (path, device) = res?;
         println!("- {}", path.display());
-        println!("  id: {:?}", device.input_id()?);
-        println!("  name: {:?}", device.name()?);
-        println!("  location: {:?}", device.phys()?);
-        println!("  unique id: {:?}", device.unique_id()?);
+        let meta = device.metadata()?;
+        println!("  id: {:?}", meta.input_id());
+        println!("  name: {:?}", meta.name());
+        println!("  location: {:?}", meta.phys());
+        println!("  unique id: {:?}", meta.unique_id());
         println!("  props: {:?}", device.props()?);
         println!("  supported events: {:?}", device.supported_events()?);
         dump_codes("EV_KEY", device.supported_keys());
@@ -136,6 +136,33 @@ impl AbsInfo {
     pub const fn resolution(&self) -> i32 {
         self.0.resolution
     }
+
+    /// Returns the [`AbsInfo::resolution`] as a floating-point number, if it is set.
+    ///
+    /// Returns [`None`] if the resolution is 0 (or negative), which means that the device hasn't
+    /// specified it.
+    #[inline]
+    pub fn to_units_per_mm(&self) -> Option<f32> {
+        if self.resolution() > 0 {
+            Some(self.resolution() as f32)
+        } else {
+            None
+        }
+    }
+
+    /// Converts an axis `value` to physical units by dividing it by the [`AbsInfo::resolution`].
+    ///
+    /// For the main position axes, the result is in **millimeters**.
+    /// For rotational and tilt axes ([`Abs::RX`], [`Abs::RY`], [`Abs::RZ`], [`Abs::TILT_X`], and
+    /// [`Abs::TILT_Y`]), the resolution is in units/radian, so the result is in **radians**
+    /// instead.
+    /// See [`AbsInfo::resolution`] for details.
+    ///
+    /// Returns [`None`] if the device hasn't specified a resolution for the axis.
+    #[inline]
+    pub fn to_mm(&self, value: i32) -> Option<f32> {
+        self.to_units_per_mm().map(|res| value as f32 / res)
+    }
 }
 
 impl fmt::Debug for AbsInfo {
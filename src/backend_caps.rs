@@ -0,0 +1,95 @@
+/// Describes which evdev and uinput features work on the current platform.
+///
+/// Returned by [`Evdev::backend_caps`] and [`BackendCaps::current`].
+///
+/// The evdev interface originates from Linux, and other platforms implementing it (like FreeBSD)
+/// do not support every feature.
+/// Instead of discovering these differences through failing `ioctl`s (or through silently missing
+/// events), cross-platform applications can query them here.
+///
+/// [`Evdev::backend_caps`]: crate::Evdev::backend_caps
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BackendCaps {
+    event_masks: bool,
+    ff: bool,
+    syn_in_supported_events: bool,
+    uinput_nonblocking: bool,
+    uinput_echoes: bool,
+}
+
+impl BackendCaps {
+    /// Returns the capabilities of the platform this crate was compiled for.
+    ///
+    /// This is based only on the target operating system.
+    /// [`Evdev::backend_caps`] additionally checks whether the running kernel supports event masks.
+    ///
+    /// [`Evdev::backend_caps`]: crate::Evdev::backend_caps
+    pub const fn current() -> Self {
+        let linux_like = !cfg!(target_os = "freebsd");
+        Self {
+            event_masks: linux_like,
+            ff: linux_like,
+            syn_in_supported_events: linux_like,
+            uinput_nonblocking: linux_like,
+            uinput_echoes: linux_like,
+        }
+    }
+
+    pub(crate) fn without_event_masks(mut self) -> Self {
+        self.event_masks = false;
+        self
+    }
+
+    /// Returns whether event masks ([`Evdev::set_event_mask`] et al.) are supported.
+    ///
+    /// [`Evdev::set_event_mask`]: crate::Evdev::set_event_mask
+    #[inline]
+    pub fn event_masks(&self) -> bool {
+        self.event_masks
+    }
+
+    /// Returns whether force-feedback is supported.
+    ///
+    /// If this is `false`, [`Evdev::supported_ff_effects`] and [`Evdev::supported_ff_features`]
+    /// don't reflect the device's capabilities, and uinput devices never receive force-feedback
+    /// requests.
+    ///
+    /// [`Evdev::supported_ff_effects`]: crate::Evdev::supported_ff_effects
+    /// [`Evdev::supported_ff_features`]: crate::Evdev::supported_ff_features
+    #[inline]
+    pub fn force_feedback(&self) -> bool {
+        self.ff
+    }
+
+    /// Returns whether [`Evdev::supported_events`] includes [`EventType::SYN`].
+    ///
+    /// `SYN` events are emitted either way.
+    ///
+    /// [`Evdev::supported_events`]: crate::Evdev::supported_events
+    /// [`EventType::SYN`]: crate::event::EventType::SYN
+    #[inline]
+    pub fn syn_in_supported_events(&self) -> bool {
+        self.syn_in_supported_events
+    }
+
+    /// Returns whether uinput devices can be put in non-blocking mode.
+    ///
+    /// See [`UinputDevice::set_nonblocking`].
+    ///
+    /// [`UinputDevice::set_nonblocking`]: crate::uinput::UinputDevice::set_nonblocking
+    #[inline]
+    pub fn uinput_nonblocking(&self) -> bool {
+        self.uinput_nonblocking
+    }
+
+    /// Returns whether LED, sound, and autorepeat events are echoed back to the uinput device that
+    /// emitted them.
+    ///
+    /// See [`UinputDevice::drain_echoes`].
+    ///
+    /// [`UinputDevice::drain_echoes`]: crate::uinput::UinputDevice::drain_echoes
+    #[inline]
+    pub fn uinput_echoes(&self) -> bool {
+        self.uinput_echoes
+    }
+}
@@ -52,6 +52,21 @@ pub trait BitValue: Copy + sealed::BitValueImpl {
 }
 
 /// A set of `V`, stored as a bit set.
+///
+/// # Examples
+///
+/// [`BitSet`]s of the event codes defined by this library can be built at compile time:
+///
+/// ```
+/// use evdevil::{bits::BitSet, event::Key};
+///
+/// const GAMEPAD_BUTTONS: BitSet<Key> =
+///     BitSet::<Key>::from_slice(&[Key::BTN_SOUTH, Key::BTN_EAST, Key::BTN_NORTH, Key::BTN_WEST]);
+///
+/// assert!(GAMEPAD_BUTTONS.contains(Key::BTN_SOUTH));
+/// assert!(!GAMEPAD_BUTTONS.contains(Key::BTN_LEFT));
+/// assert_eq!(GAMEPAD_BUTTONS.len(), 4);
+/// ```
 pub struct BitSet<V: BitValue> {
     pub(crate) words: Array<V>,
 }
@@ -69,6 +84,11 @@ impl<V: BitValue> Default for BitSet<V> {
 }
 
 impl<V: BitValue> BitSet<V> {
+    /// An empty bit set that doesn't contain any values.
+    ///
+    /// This is the same as [`BitSet::new`].
+    pub const EMPTY: Self = Self::new();
+
     /// Creates an empty bit set that doesn't contain any values.
     pub const fn new() -> Self {
         Self {
@@ -76,6 +96,25 @@ impl<V: BitValue> BitSet<V> {
         }
     }
 
+    /// Creates a [`BitSet`] from an iterator over raw event codes.
+    ///
+    /// This is useful when the set is obtained from a source that doesn't use `evdevil`'s types,
+    /// for example when deserializing a capability list.
+    ///
+    /// Codes that exceed [`<V as BitValue>::MAX`][BitValue::MAX] are silently ignored, and
+    /// duplicate codes are only stored once.
+    pub fn from_raw_codes(codes: impl IntoIterator<Item = u16>) -> Self {
+        let max = V::MAX.into_index();
+        let mut this = Self::new();
+        for code in codes {
+            let index = usize::from(code);
+            if index <= max {
+                this.insert(V::from_index(index));
+            }
+        }
+        this
+    }
+
     /// Returns a reference to the underlying [`Word`]s making up this [`BitSet`].
     ///
     /// Note that the [`Word`] type varies in size and endianness between platforms, so if you want
@@ -179,6 +218,15 @@ impl<V: BitValue> BitSet<V> {
         }
     }
 
+    /// Returns an iterator over the raw event codes of all values in `self`.
+    ///
+    /// This is the inverse of [`BitSet::from_raw_codes`], and is useful when passing the set to
+    /// something that works with raw numeric codes.
+    pub fn iter_raw(&self) -> impl Iterator<Item = u16> + '_ {
+        // All `BitValue`s are `u16`-sized, so this cannot truncate.
+        self.iter().map(|value| value.into_index() as u16)
+    }
+
     /// Returns an iterator over all values that are contained in either `self` or `other`, but not
     /// both.
     pub(crate) fn symmetric_difference<'a>(
@@ -433,6 +481,47 @@ mod tests {
         );
     }
 
+    #[test]
+    fn from_raw_codes() {
+        let set = BitSet::<Rel>::from_raw_codes([0, 1, 1, 0xffff, Rel::MAX.raw() + 1]);
+        assert_eq!(set.iter().collect::<Vec<_>>(), &[Rel::X, Rel::Y]);
+
+        let set = BitSet::<Key>::from_raw_codes([Key::MAX.raw()]);
+        assert_eq!(set.iter().collect::<Vec<_>>(), &[Key::MAX]);
+    }
+
+    #[test]
+    fn const_sets() {
+        const EMPTY: BitSet<Key> = BitSet::EMPTY;
+        assert!(EMPTY.is_empty());
+
+        const SET: BitSet<Key> = BitSet::<Key>::from_slice(&[Key::KEY_A, Key::MAX, Key::KEY_A]);
+        assert_eq!(SET, BitSet::from_iter([Key::KEY_A, Key::MAX]));
+        assert_eq!(BitSet::<Abs>::from_slice(&[]), BitSet::new());
+    }
+
+    #[test]
+    #[should_panic = "value out of range for `BitSet`"]
+    fn from_slice_out_of_range() {
+        // Still fits in the first word, but is larger than `Rel::MAX`.
+        let _ = BitSet::<Rel>::from_slice(&[Rel::from_raw(Rel::MAX.raw() + 1)]);
+    }
+
+    #[test]
+    fn iter_raw() {
+        let set = BitSet::from_iter([Key::KEY_A, Key::BTN_TRIGGER_HAPPY1, Key::MAX]);
+        let raw = set.iter_raw().collect::<Vec<_>>();
+        assert_eq!(
+            raw,
+            &[
+                Key::KEY_A.raw(),
+                Key::BTN_TRIGGER_HAPPY1.raw(),
+                Key::MAX.raw()
+            ]
+        );
+        assert_eq!(BitSet::<Key>::from_raw_codes(raw), set);
+    }
+
     #[test]
     fn symmdiff() {
         let mut a = BitSet::new();
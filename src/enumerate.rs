@@ -4,9 +4,11 @@
 //! (via [`enumerate`]), or whether they also want to receive any devices that will be hot-plugged
 //! in later (via [`enumerate_hotplug`]).
 //!
-//! Device enumeration is always blocking, and cannot be made non-blocking or `async`.
+//! Device enumeration is always blocking, and cannot be made non-blocking.
 //! For interactive applications, it is recommended to perform device enumeration in a dedicated
 //! thread.
+//! When the `"tokio"` or `"async-io"` feature is enabled, [`enumerate_async`] does exactly that,
+//! and delivers the devices to an `async` task.
 
 use std::{
     cmp,
@@ -14,13 +16,20 @@ use std::{
     fs::{self, ReadDir},
     io,
     os::unix::fs::FileTypeExt as _,
-    path::PathBuf,
+    path::{Path, PathBuf},
     thread,
     time::Duration,
     vec,
 };
+#[cfg(any(feature = "tokio", feature = "async-io"))]
+use std::{
+    collections::VecDeque,
+    future,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+};
 
-use crate::{Evdev, hotplug::HotplugMonitor};
+use crate::{Evdev, InputId, InputIdPattern, hotplug::HotplugMonitor};
 
 /// Enumerates all currently plugged-in [`Evdev`] devices.
 ///
@@ -47,9 +56,81 @@ use crate::{Evdev, hotplug::HotplugMonitor};
 /// # Ok::<_, std::io::Error>(())
 /// ```
 pub fn enumerate() -> io::Result<Enumerate> {
-    Ok(Enumerate {
-        read_dir: fs::read_dir("/dev/input")?,
-    })
+    enumerate_in("/dev/input")
+}
+
+/// Enumerates all [`Evdev`] devices in a custom device directory.
+///
+/// This works like [`enumerate`], but looks for `event*` device nodes in `dir` instead of
+/// `/dev/input`.
+/// This is useful in containers, chroots, or test environments where the device nodes are located
+/// (or bind-mounted) elsewhere.
+/// Like with [`Evdev::open`], device nodes outside of `/dev/input` are only opened if they are
+/// verified to be `evdev` nodes.
+///
+/// # Errors
+///
+/// This returns an error if `dir` can not be read.
+/// Like with [`enumerate`], errors encountered while opening individual devices are yielded by the
+/// returned iterator.
+pub fn enumerate_in(dir: impl AsRef<Path>) -> io::Result<Enumerate> {
+    let dir = dir.as_ref();
+    let read_dir = fs::read_dir(dir).map_err(|e| {
+        io::Error::new(
+            e.kind(),
+            format!("failed to read device directory '{}': {e}", dir.display()),
+        )
+    })?;
+    Ok(Enumerate { read_dir })
+}
+
+/// Finds all currently plugged-in [`Evdev`] devices with the given [`InputId`].
+///
+/// This is meant for reopening a specific device after it was unplugged and plugged back in.
+/// If `match_version` is `false`, the device version is ignored, so that devices are still found
+/// after a firmware update.
+///
+/// Multiple devices can share the same [`InputId`] (for example, two identical controllers, or a
+/// device that exposes several evdev interfaces), so this returns all of them.
+/// Devices that can not be opened or queried are skipped.
+///
+/// # Errors
+///
+/// This returns an error if `/dev/input` can not be read.
+///
+/// # Examples
+///
+/// ```no_run
+/// use evdevil::{Bus, InputId, enumerate::find_by_input_id};
+///
+/// let id = InputId::new(Bus::USB, 0x045e, 0x028e, 0x0110);
+/// for evdev in find_by_input_id(id, false)? {
+///     println!("found {:?}", evdev.name()?);
+/// }
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub fn find_by_input_id(id: InputId, match_version: bool) -> io::Result<Vec<Evdev>> {
+    let mut pattern = InputIdPattern::new(id);
+    if !match_version {
+        pattern = pattern.any_version();
+    }
+
+    let mut devices = Vec::new();
+    for res in enumerate()? {
+        let (path, evdev) = match res {
+            Ok(dev) => dev,
+            Err(e) => {
+                debug!("skipping device: {e}");
+                continue;
+            }
+        };
+        match evdev.input_id() {
+            Ok(input_id) if input_id.matches(&pattern) => devices.push(evdev),
+            Ok(_) => {}
+            Err(e) => debug!("skipping device at `{}`: {e}", path.display()),
+        }
+    }
+    Ok(devices)
 }
 
 /// Enumerates all currently plugged-in [`Evdev`] devices, and future hotplugged devices.
@@ -82,9 +163,118 @@ pub fn enumerate_hotplug() -> io::Result<EnumerateHotplug> {
     EnumerateHotplug::new()
 }
 
+/// Asynchronously enumerates all currently plugged-in [`Evdev`] devices.
+///
+/// This performs the same enumeration as [`enumerate`], but opens the devices on a background
+/// thread, so that the executor is not blocked while device files are being opened.
+/// The devices are delivered through the returned [`AsyncEnumerate`].
+///
+/// # Errors
+///
+/// This returns an error if the background thread could not be spawned.
+/// Errors encountered during enumeration are yielded by [`AsyncEnumerate::next`].
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn dox() -> std::io::Result<()> {
+/// use evdevil::enumerate::enumerate_async;
+///
+/// let mut devices = enumerate_async()?;
+/// while let Some(res) = devices.next().await {
+///     match res {
+///         Ok((path, evdev)) => println!("{}: {:?}", path.display(), evdev.name()),
+///         Err(e) => eprintln!("error during device enumeration: {e}"),
+///     }
+/// }
+/// # Ok(()) }
+/// ```
+#[cfg_attr(docsrs, doc(cfg(any(feature = "tokio", feature = "async-io"))))]
+#[cfg(any(feature = "tokio", feature = "async-io"))]
+pub fn enumerate_async() -> io::Result<AsyncEnumerate> {
+    AsyncEnumerate::new()
+}
+
+/// Asynchronous stream of evdev devices on the system.
+///
+/// Returned by [`enumerate_async`].
+///
+/// Note that this type does not implement the `Stream` or `AsyncIterator` traits, to avoid
+/// depending on the `futures` crate.
+/// To fetch devices, use [`AsyncEnumerate::next`] or [`AsyncEnumerate::poll_next`].
+#[cfg_attr(docsrs, doc(cfg(any(feature = "tokio", feature = "async-io"))))]
+#[cfg(any(feature = "tokio", feature = "async-io"))]
+#[derive(Debug)]
+pub struct AsyncEnumerate {
+    shared: Arc<Mutex<Shared>>,
+}
+
+#[cfg(any(feature = "tokio", feature = "async-io"))]
+#[derive(Debug, Default)]
+struct Shared {
+    results: VecDeque<io::Result<(PathBuf, Evdev)>>,
+    done: bool,
+    waker: Option<Waker>,
+}
+
+#[cfg(any(feature = "tokio", feature = "async-io"))]
+impl AsyncEnumerate {
+    fn new() -> io::Result<Self> {
+        let shared = Arc::new(Mutex::new(Shared::default()));
+        let thread_shared = shared.clone();
+        thread::Builder::new()
+            .name("evdev-enumerate".into())
+            .spawn(move || {
+                let push = |res: Option<io::Result<(PathBuf, Evdev)>>| {
+                    let mut shared = thread_shared.lock().unwrap();
+                    match res {
+                        Some(res) => shared.results.push_back(res),
+                        None => shared.done = true,
+                    }
+                    if let Some(waker) = shared.waker.take() {
+                        waker.wake();
+                    }
+                };
+
+                match enumerate() {
+                    Ok(iter) => iter.for_each(|res| push(Some(res))),
+                    Err(e) => push(Some(Err(e))),
+                }
+                push(None);
+            })?;
+        Ok(Self { shared })
+    }
+
+    /// Asynchronously fetches the next device.
+    ///
+    /// Returns [`None`] once all devices have been yielded.
+    pub async fn next(&mut self) -> Option<io::Result<(PathBuf, Evdev)>> {
+        future::poll_fn(|cx| self.poll_next(cx)).await
+    }
+
+    /// Attempts to fetch the next device.
+    ///
+    /// This mirrors the signature of `Stream::poll_next`, and can be used to implement `Stream` in
+    /// a wrapper type.
+    pub fn poll_next(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<io::Result<(PathBuf, Evdev)>>> {
+        let mut shared = self.shared.lock().unwrap();
+        match shared.results.pop_front() {
+            Some(res) => Poll::Ready(Some(res)),
+            None if shared.done => Poll::Ready(None),
+            None => {
+                shared.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
 /// Iterator over evdev devices on the system.
 ///
-/// Returned by [`enumerate`].
+/// Returned by [`enumerate`] and [`enumerate_in`].
 ///
 /// If a device is plugged into the system after [`enumerate`] has been called, it is unspecified
 /// whether [`Enumerate`] will yield the new device.
@@ -125,7 +315,7 @@ impl Iterator for Enumerate {
                 continue;
             }
 
-            match Evdev::open_unchecked(&path) {
+            match Evdev::open_impl(&path) {
                 Ok(dev) => return Some(Ok((path, dev))),
                 // If a device is unplugged in the middle of enumeration (before it can be opened),
                 // skip it, since yielding this error to the application is pretty useless.
@@ -290,7 +480,7 @@ impl Iterator for EnumerateHotplug {
 
 #[cfg(test)]
 mod tests {
-    use crate::{event::Key, uinput::UinputDevice};
+    use crate::{Bus, event::Key, test::pair, uinput::UinputDevice};
 
     use super::*;
 
@@ -306,6 +496,54 @@ mod tests {
         assert!(e.monitor.is_some());
     }
 
+    #[cfg(any(feature = "tokio", feature = "async-io"))]
+    #[test]
+    fn async_enumerate() -> io::Result<()> {
+        use crate::util::r#async::Runtime;
+
+        // `/dev/input` may not exist; then both versions should yield no devices.
+        let sync = enumerate().map_or(0, |iter| iter.filter(Result::is_ok).count());
+
+        let rt = Runtime::new()?;
+        let _guard = rt.enter();
+        let count = rt.block_on(async {
+            let mut devices = enumerate_async()?;
+            let mut count = 0;
+            while let Some(res) = devices.next().await {
+                if res.is_ok() {
+                    count += 1;
+                }
+            }
+            io::Result::Ok(count)
+        })?;
+
+        // Devices may be hotplugged in between, but that's unlikely.
+        assert_eq!(count, sync);
+        Ok(())
+    }
+
+    #[test]
+    fn find_by_input_id() -> io::Result<()> {
+        if !fs::exists("/dev/uinput")? {
+            eprintln!("`/dev/uinput` doesn't exist, probably running under QEMU");
+            return Ok(());
+        }
+
+        let id = InputId::new(Bus::VIRTUAL, 0x1234, 0xf1d0, 3);
+        let (_uinput, evdev) = pair(|b| b.with_input_id(id)?.with_keys([Key::BTN_LEFT]))?;
+
+        let found = super::find_by_input_id(id, true)?;
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].name()?, evdev.name()?);
+
+        let newer = InputId::new(Bus::VIRTUAL, 0x1234, 0xf1d0, 4);
+        assert!(super::find_by_input_id(newer, true)?.is_empty());
+        let found = super::find_by_input_id(newer, false)?;
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].input_id()?, id);
+        Ok(())
+    }
+
     #[test]
     fn hotplug_enumerate() {
         if !fs::exists("/dev/uinput").unwrap() {
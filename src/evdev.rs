@@ -2,23 +2,32 @@ use std::{
     error::Error,
     ffi::{c_char, c_int, c_uint, c_void},
     fmt,
-    fs::File,
+    fs::{self, File},
     io,
-    mem::MaybeUninit,
+    mem::{ManuallyDrop, MaybeUninit},
+    ops::Deref,
     os::{
         fd::{AsFd, AsRawFd, IntoRawFd, OwnedFd},
-        unix::prelude::{BorrowedFd, RawFd},
+        unix::{
+            fs::{FileTypeExt as _, MetadataExt as _, OpenOptionsExt as _},
+            prelude::{BorrowedFd, RawFd},
+        },
     },
-    path::Path,
-    slice,
-    time::Instant,
+    path::{Path, PathBuf},
+    slice, str,
+    sync::{
+        Arc, OnceLock,
+        atomic::{AtomicBool, AtomicI32, AtomicU32, Ordering},
+    },
+    thread,
+    time::{Duration, Instant, SystemTime},
 };
 
 use libc::clockid_t;
 use uoctl::Ioctl;
 
 use crate::{
-    AbsInfo, InputProp, KeyRepeat, KeymapEntry, Version,
+    AbsInfo, BackendCaps, InputProp, KeyRepeat, KeymapEntry, PointerCaps, Version,
     bits::{BitSet, BitValue, Word},
     event::{
         Abs, EventType, ForceFeedbackEvent, InputEvent, Key, Led, LedEvent, Misc, Rel, Sound,
@@ -29,13 +38,18 @@ use crate::{
     keymap_entry::Scancode,
     raw::input::{
         EVIOCGABS, EVIOCGBIT, EVIOCGEFFECTS, EVIOCGID, EVIOCGKEY, EVIOCGKEYCODE_V2, EVIOCGLED,
-        EVIOCGMASK, EVIOCGNAME, EVIOCGPHYS, EVIOCGPROP, EVIOCGRAB, EVIOCGREP, EVIOCGSND, EVIOCGSW,
-        EVIOCGUNIQ, EVIOCGVERSION, EVIOCREVOKE, EVIOCRMFF, EVIOCSABS, EVIOCSCLOCKID, EVIOCSFF,
-        EVIOCSKEYCODE_V2, EVIOCSMASK, EVIOCSREP, INPUT_KEYMAP_BY_INDEX, input_mask,
+        EVIOCGMASK, EVIOCGMTSLOTS, EVIOCGNAME, EVIOCGPHYS, EVIOCGPROP, EVIOCGRAB, EVIOCGREP,
+        EVIOCGSND, EVIOCGSW, EVIOCGUNIQ, EVIOCGVERSION, EVIOCREVOKE, EVIOCRMFF, EVIOCSABS,
+        EVIOCSCLOCKID, EVIOCSFF, EVIOCSKEYCODE_V2, EVIOCSMASK, EVIOCSREP, INPUT_KEYMAP_BY_INDEX,
+        input_mask,
     },
     read_raw,
-    reader::EventReader,
-    util::{block_until_readable, is_readable, set_nonblocking},
+    reader::{EventReader, MAX_MT_SLOTS, SlotSnapshot, report_or_dropped},
+    uinput::{AbsSetup, EventWriter},
+    util::{
+        access_mode, block_until_readable, clock_now, is_nonblocking, is_readable, set_cloexec,
+        set_nonblocking,
+    },
     write_raw,
 };
 
@@ -96,6 +110,18 @@ use crate::{
 #[derive(Debug)]
 pub struct Evdev {
     pub(crate) file: File,
+    /// Shared with clones, since they refer to the same open file.
+    ff_settings: Arc<FfSettings>,
+    /// Shared with clones, since the grab also belongs to the open file.
+    grab: Arc<GrabTracker>,
+    /// Set by [`Evdev::revoke`]. Shared with clones for the same reason.
+    revoked: Arc<AtomicBool>,
+    /// The clock selected via [`Evdev::set_clockid`]. Also shared with clones.
+    clockid: Arc<AtomicI32>,
+    /// Cached result of [`Evdev::input_id`], since the hardware IDs never change.
+    input_id: OnceLock<InputId>,
+    /// Cached result of [`Evdev::supported_ff_features`], which never change either.
+    ff_features: OnceLock<BitSet<ff::Feature>>,
 }
 
 impl AsFd for Evdev {
@@ -115,6 +141,7 @@ impl AsRawFd for Evdev {
 impl IntoRawFd for Evdev {
     #[inline]
     fn into_raw_fd(self) -> RawFd {
+        self.grab.forget();
         self.file.into_raw_fd()
     }
 }
@@ -122,6 +149,7 @@ impl IntoRawFd for Evdev {
 impl From<Evdev> for OwnedFd {
     #[inline]
     fn from(value: Evdev) -> Self {
+        value.grab.forget();
         value.file.into()
     }
 }
@@ -133,6 +161,11 @@ impl Evdev {
     /// *"joydev"* device (`/dev/input/js`) and not to a legacy *"mousedev"* (`/dev/input/mouse` or
     /// `/dev/input/mice`).
     ///
+    /// On Linux, devices outside of `/dev/input` (for example, in containers or chroots where the
+    /// device nodes are bind-mounted elsewhere) are also accepted.
+    /// They are opened first, and then verified to be `evdev` nodes (via their device number and
+    /// `/sys/dev/char`) before any `ioctl` is sent to them.
+    ///
     /// # Permissions
     ///
     /// This method will attempt to open `path` with read-write permissions, fall back to read-only
@@ -142,10 +175,15 @@ impl Evdev {
     /// If all of these attempts fail with a [`io::ErrorKind::PermissionDenied`] error, this method
     /// will return that error to the caller.
     ///
+    /// The device is opened with `O_CLOEXEC`, so the file descriptor will not be inherited by
+    /// child processes spawned via `exec`.
+    /// Use [`Evdev::set_cloexec`] to change this.
+    ///
     /// # Errors
     ///
     /// - An [`io::ErrorKind::InvalidInput`] error will be returned if `path` doesn't refer to a
-    ///   path matching `/dev/input/event*` (after resolving symlinks).
+    ///   path matching `/dev/input/event*` (after resolving symlinks), or to another `evdev` device
+    ///   node.
     /// - A [`io::ErrorKind::PermissionDenied`] error will be returned if the user does not have
     ///   permission to open the device in read-write, write-only, or read-only mode.
     /// - An [`io::ErrorKind::NotFound`] error, **or** an error with [`io::Error::raw_os_error`]
@@ -157,7 +195,69 @@ impl Evdev {
         Self::open_impl(path)
     }
 
-    fn open_impl(path: &Path) -> io::Result<Self> {
+    /// Opens an `evdev` node, retrying if it is not accessible yet.
+    ///
+    /// When a device has just been plugged in (or created via `uinput`), its device node may not
+    /// exist yet, or may briefly have incorrect permissions until `udev` has processed it.
+    /// This method calls [`Evdev::open`] up to `max_attempts` times (but at least once), sleeping
+    /// for `delay` between attempts, as long as it fails with [`io::ErrorKind::PermissionDenied`]
+    /// or [`io::ErrorKind::NotFound`].
+    /// All other errors are returned immediately.
+    ///
+    /// Where possible, prefer waiting for hotplug events via
+    /// [`HotplugMonitor`][crate::hotplug::HotplugMonitor] instead: they are only delivered once
+    /// the device is ready to be opened, which avoids this race entirely.
+    ///
+    /// # Errors
+    ///
+    /// If all attempts fail, the error returned by the last attempt is returned.
+    pub fn open_with_retry<P: AsRef<Path>>(
+        path: P,
+        max_attempts: u32,
+        delay: Duration,
+    ) -> io::Result<Self> {
+        let path = path.as_ref();
+        let mut attempts = 1;
+        loop {
+            match Self::open_impl(path) {
+                Err(e)
+                    if attempts < max_attempts
+                        && matches!(
+                            e.kind(),
+                            io::ErrorKind::PermissionDenied | io::ErrorKind::NotFound
+                        ) =>
+                {
+                    debug!(
+                        "failed to open '{}' (attempt {attempts}/{max_attempts}): {e}; retrying",
+                        path.display()
+                    );
+                    attempts += 1;
+                    thread::sleep(delay);
+                }
+                res => return res,
+            }
+        }
+    }
+
+    /// Opens an `evdev` node like [`Evdev::open`], and returns the [`AccessMode`] it was opened
+    /// with.
+    ///
+    /// [`Evdev::open`] falls back to read-only or write-only mode if the user lacks permission to
+    /// open the device in read-write mode, which silently disables some functionality (like
+    /// force-feedback or receiving events).
+    /// This method allows applications to detect that right away, and to inform the user about
+    /// the insufficient permissions.
+    ///
+    /// # Errors
+    ///
+    /// This returns the same errors as [`Evdev::open`].
+    pub fn open_reporting<P: AsRef<Path>>(path: P) -> io::Result<(Self, AccessMode)> {
+        let this = Self::open_impl(path.as_ref())?;
+        let mode = this.access_mode()?;
+        Ok((this, mode))
+    }
+
+    pub(crate) fn open_impl(path: &Path) -> io::Result<Self> {
         const PREFIX: &[u8] = b"/dev/input/event";
         if path.as_os_str().as_encoded_bytes().starts_with(PREFIX) {
             return Self::open_unchecked(path);
@@ -166,37 +266,55 @@ impl Evdev {
         // If the path is not in `/dev/input/event*`, it might be a symlink or relative path
         // pointing there.
         let path = path.canonicalize()?;
-        if !path
-            .as_os_str()
-            .as_encoded_bytes()
-            .starts_with(b"/dev/input/event")
-        {
-            return Err(io::Error::new(
-                io::ErrorKind::InvalidInput,
-                format!(
-                    "evdev device path '{}' must match '/dev/input/event*'",
-                    path.display()
-                ),
-            ));
+        if path.as_os_str().as_encoded_bytes().starts_with(PREFIX) {
+            return Self::open_unchecked(&path);
         }
 
-        Self::open_unchecked(&path)
+        // Otherwise, it might be an evdev node in a non-standard device directory.
+        // Opening other device nodes can have side effects (eg. arming a watchdog), and sending
+        // evdev ioctls to another driver can be memory-unsafe, so the node is checked before it is
+        // opened, and checked again afterwards to make sure it wasn't replaced in the meantime.
+        let now = Instant::now();
+        let meta = fs::metadata(&path)?;
+        if !is_evdev_node(&meta)? {
+            return Err(not_an_evdev_node(&path));
+        }
+
+        let file = Self::open_file(&path, libc::O_NOFOLLOW | libc::O_NOCTTY)?;
+        let opened = file.metadata()?;
+        if !opened.file_type().is_char_device() || opened.rdev() != meta.rdev() {
+            return Err(not_an_evdev_node(&path));
+        }
+
+        Self::init(file, &path, now)
     }
 
     /// Opens `path` without checking that it is one of the `/dev/input/event*` paths.
     pub(crate) fn open_unchecked(path: &Path) -> io::Result<Self> {
         let now = Instant::now();
+        let file = Self::open_file(path, 0)?;
+        Self::init(file, path, now)
+    }
 
-        let file = match Self::try_open(path) {
-            Ok(file) => file,
-            Err(e) => {
-                return Err(io::Error::new(
-                    e.kind(),
-                    format!("failed to open '{}': {e}", path.display()),
-                ));
-            }
+    fn open_file(path: &Path, flags: c_int) -> io::Result<File> {
+        Self::try_open(path, flags).map_err(|e| {
+            io::Error::new(
+                e.kind(),
+                format!("failed to open '{}': {e}", path.display()),
+            )
+        })
+    }
+
+    fn init(file: File, path: &Path, now: Instant) -> io::Result<Self> {
+        let this = Self {
+            file,
+            ff_settings: Arc::new(FfSettings::new()),
+            grab: Arc::default(),
+            revoked: Arc::default(),
+            clockid: Arc::new(AtomicI32::new(libc::CLOCK_REALTIME)),
+            input_id: OnceLock::new(),
+            ff_features: OnceLock::new(),
         };
-        let this = Self { file };
         let version = this.driver_version()?;
         debug!(
             "opened '{}' in {:?}; driver version {version}",
@@ -206,8 +324,13 @@ impl Evdev {
         Ok(this)
     }
 
-    fn try_open(path: &Path) -> io::Result<File> {
-        match File::options().read(true).write(true).open(path) {
+    fn try_open(path: &Path, flags: c_int) -> io::Result<File> {
+        match File::options()
+            .read(true)
+            .write(true)
+            .custom_flags(flags)
+            .open(path)
+        {
             Ok(file) => return Ok(file),
             Err(e) if e.kind() == io::ErrorKind::PermissionDenied => {
                 warn!(
@@ -218,7 +341,7 @@ impl Evdev {
             Err(e) => return Err(e),
         }
 
-        match File::options().read(true).open(path) {
+        match File::options().read(true).custom_flags(flags).open(path) {
             Ok(file) => return Ok(file),
             Err(e) if e.kind() == io::ErrorKind::PermissionDenied => {
                 warn!(
@@ -229,7 +352,7 @@ impl Evdev {
             Err(e) => return Err(e),
         }
 
-        File::options().write(true).open(path)
+        File::options().write(true).custom_flags(flags).open(path)
     }
 
     /// Creates an [`Evdev`] instance from a bare file descriptor.
@@ -239,13 +362,52 @@ impl Evdev {
     /// `owned_fd` must refer to a character device managed by the input system.
     /// If it doesn't, the evdev ioctls will be sent to the wrong driver, which may have a
     /// colliding ioctl number with memory-unsafe semantics when invoked this way.
+    ///
+    /// The close-on-exec flag of `owned_fd` is left unchanged. If the file descriptor was not
+    /// opened with `O_CLOEXEC`, consider calling [`Evdev::set_cloexec`] to prevent it from leaking
+    /// into child processes.
     #[inline]
     pub unsafe fn from_owned_fd(owned_fd: OwnedFd) -> Self {
         Self {
             file: File::from(owned_fd),
+            ff_settings: Arc::new(FfSettings::new()),
+            grab: Arc::default(),
+            revoked: Arc::default(),
+            clockid: Arc::new(AtomicI32::new(libc::CLOCK_REALTIME)),
+            input_id: OnceLock::new(),
+            ff_features: OnceLock::new(),
         }
     }
 
+    /// Returns the [`AccessMode`] this handle was opened with.
+    ///
+    /// See [`Evdev::open_reporting`].
+    pub fn access_mode(&self) -> io::Result<AccessMode> {
+        Ok(match access_mode(self.as_raw_fd())? {
+            libc::O_RDONLY => AccessMode::ReadOnly,
+            libc::O_WRONLY => AccessMode::WriteOnly,
+            _ => AccessMode::ReadWrite,
+        })
+    }
+
+    /// Returns whether events can be read from this handle.
+    ///
+    /// This is `false` if [`Evdev::open`] had to fall back to opening the device in write-only
+    /// mode.
+    /// Such an [`Evdev`] can still be used to control LEDs or force-feedback effects, but it cannot
+    /// be used to receive events or be turned into an [`EventReader`].
+    pub fn can_read(&self) -> io::Result<bool> {
+        Ok(access_mode(self.as_raw_fd())? != libc::O_WRONLY)
+    }
+
+    /// Returns whether events can be written to this handle.
+    ///
+    /// This is `false` if [`Evdev::open`] had to fall back to opening the device in read-only
+    /// mode.
+    pub fn can_write(&self) -> io::Result<bool> {
+        Ok(access_mode(self.as_raw_fd())? != libc::O_RDONLY)
+    }
+
     /// Moves this handle into or out of non-blocking mode.
     ///
     /// Returns whether the [`Evdev`] was previously in non-blocking mode.
@@ -268,9 +430,53 @@ impl Evdev {
         set_nonblocking(self.as_raw_fd(), nonblocking)
     }
 
+    /// Returns whether the [`Evdev`] is currently in non-blocking mode.
+    ///
+    /// Unlike [`Evdev::set_nonblocking`], this does not change the mode.
+    ///
+    /// The blocking mode is shared with all handles created via [`Evdev::try_clone`].
+    pub fn is_nonblocking(&self) -> io::Result<bool> {
+        is_nonblocking(self.as_raw_fd())
+    }
+
+    /// Sets or clears the close-on-exec flag (`FD_CLOEXEC`) of this handle's file descriptor.
+    ///
+    /// Returns whether the flag was previously set.
+    ///
+    /// When the flag is set, the file descriptor is closed automatically when the process calls
+    /// `exec`, so that spawned child processes do not gain access to the input device.
+    /// [`Evdev::open`] sets the flag by default, and it should only be cleared when the file
+    /// descriptor is deliberately passed to a child process.
+    ///
+    /// **Note**: Unlike non-blocking mode, the flag belongs to the file descriptor, so it is *not*
+    /// shared with handles created by [`Evdev::try_clone`].
+    #[doc(alias = "FD_CLOEXEC")]
+    pub fn set_cloexec(&self, cloexec: bool) -> io::Result<bool> {
+        set_cloexec(self.as_raw_fd(), cloexec)
+    }
+
+    /// Moves this handle into non-blocking mode until the returned guard is dropped.
+    ///
+    /// When the [`NonblockingGuard`] is dropped, the [`Evdev`] is moved back into whatever mode it
+    /// was in before this method was called.
+    /// This also happens on early returns and during unwinding, so it is a more robust alternative
+    /// to pairing up calls to [`Evdev::set_nonblocking`] manually.
+    ///
+    /// The guard dereferences to the [`Evdev`], so it can be used to read events directly.
+    pub fn nonblocking_scope(&self) -> io::Result<NonblockingGuard<'_>> {
+        let was_nonblocking = self.set_nonblocking(true)?;
+        Ok(NonblockingGuard {
+            evdev: self,
+            was_nonblocking,
+        })
+    }
+
     /// Creates a new [`Evdev`] instance that refers to the same underlying file handle.
     ///
     /// All state of the [`Evdev`] will be shared between the instances.
+    /// This includes the blocking mode: putting one of the instances in non-blocking mode via
+    /// [`Evdev::set_nonblocking`] affects all of them.
+    /// Use [`Evdev::reopen`] to get an independent handle instead.
     ///
     /// **Note**: Care must be taken when using this method.
     /// Functionality in this crate (like [`EventReader`]) may assume that no other file handle is
@@ -279,9 +485,116 @@ impl Evdev {
     pub fn try_clone(&self) -> io::Result<Self> {
         Ok(Self {
             file: self.file.try_clone()?,
+            ff_settings: self.ff_settings.clone(),
+            grab: self.grab.clone(),
+            revoked: self.revoked.clone(),
+            clockid: self.clockid.clone(),
+            input_id: self.input_id.clone(),
+            ff_features: self.ff_features.clone(),
         })
     }
 
+    /// Opens the device this [`Evdev`] refers to again, creating an independent handle.
+    ///
+    /// Unlike [`Evdev::try_clone`], the returned [`Evdev`] has its own open file description, so
+    /// it has its own blocking mode, event queue, event masks, and grab state.
+    /// This is useful when one thread wants to perform blocking reads while another polls the
+    /// device in non-blocking mode.
+    ///
+    /// Both handles receive all events emitted by the device, like two [`Evdev`]s opened via
+    /// [`Evdev::open`] would.
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if the [`Evdev`] has been revoked via [`Evdev::revoke`], or if the
+    /// device has been unplugged.
+    pub fn reopen(&self) -> io::Result<Self> {
+        self.check_revoked()?;
+
+        // Opening the `/proc/self/fd` entry creates a new open file description for the same file.
+        let fd_path = format!("/proc/self/fd/{}", self.as_raw_fd());
+        match Self::open_unchecked(Path::new(&fd_path)) {
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            res => return res,
+        }
+
+        // `/proc` may not be mounted (or might not exist, like on FreeBSD), so fall back to looking
+        // for the device node in `/dev/input`.
+        let rdev = self.file.metadata()?.rdev();
+        for entry in fs::read_dir("/dev/input")? {
+            let path = entry?.path();
+            if !path
+                .file_name()
+                .is_some_and(|name| name.as_encoded_bytes().starts_with(b"event"))
+            {
+                continue;
+            }
+            match fs::metadata(&path) {
+                Ok(meta) if meta.file_type().is_char_device() && meta.rdev() == rdev => {
+                    return Self::open_unchecked(&path);
+                }
+                _ => {}
+            }
+        }
+        Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            "could not find the device node to reopen",
+        ))
+    }
+
+    /// Returns a [`DeviceKey`] that identifies the input device this [`Evdev`] refers to.
+    ///
+    /// This is unrelated to the hardware IDs returned by [`Evdev::input_id`]: the [`DeviceKey`] is
+    /// derived from the device number of the `evdev` node (via `fstat`), so it is unique among
+    /// all currently plugged-in devices.
+    ///
+    /// All [`Evdev`]s referring to the same device have the same [`DeviceKey`], no matter whether
+    /// they were created via [`Evdev::try_clone`] or by opening the device (or a symlink to it)
+    /// again.
+    /// When a device is unplugged and plugged back in, it may or may not end up with the same
+    /// [`DeviceKey`], and after a device is unplugged its [`DeviceKey`] may be reused for a
+    /// different device.
+    #[doc(alias = "fstat")]
+    pub fn id(&self) -> io::Result<DeviceKey> {
+        let meta = self.file.metadata()?;
+        Ok(DeviceKey {
+            dev: meta.dev(),
+            rdev: meta.rdev(),
+        })
+    }
+
+    /// Returns the stable `/dev/input/by-id` symlink pointing to this device, if one exists.
+    ///
+    /// The numbers in `/dev/input/eventN` paths are assigned in the order devices are plugged in,
+    /// so they can change across reboots and replugs.
+    /// The symlinks in `/dev/input/by-id` are created by `udev` and are derived from the device's
+    /// bus, name and serial number instead, which makes them suitable for remembering which device
+    /// a user has selected.
+    ///
+    /// Returns [`None`] if `/dev/input/by-id` doesn't exist, or contains no symlink pointing to this
+    /// device (`udev` only creates them for some devices; virtual devices typically have none).
+    pub fn by_id_path(&self) -> io::Result<Option<PathBuf>> {
+        const BY_ID: &str = "/dev/input/by-id";
+
+        let rdev = self.file.metadata()?.rdev();
+        let read_dir = match fs::read_dir(BY_ID) {
+            Ok(read_dir) => read_dir,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        for entry in read_dir {
+            let path = entry?.path();
+            // Follows the symlink. Dangling links are skipped.
+            match fs::metadata(&path) {
+                Ok(meta) if meta.file_type().is_char_device() && meta.rdev() == rdev => {
+                    return Ok(Some(path));
+                }
+                _ => {}
+            }
+        }
+        Ok(None)
+    }
+
     /// Executes `ioctl` and adds context to the error.
     pub(crate) unsafe fn ioctl<T>(
         &self,
@@ -289,62 +602,93 @@ impl Evdev {
         ioctl: Ioctl<T>,
         arg: T,
     ) -> io::Result<c_int> {
+        self.check_revoked()?;
         match unsafe { ioctl.ioctl(self, arg) } {
             Ok(ok) => Ok(ok),
-            Err(e) => {
-                #[derive(Debug)]
-                struct WrappedError {
-                    cause: io::Error,
-                    ioctl: &'static str,
-                }
+            Err(e) => Err(wrap_ioctl_error(name, e)),
+        }
+    }
 
-                impl fmt::Display for WrappedError {
-                    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-                        write!(f, "ioctl {} failed", self.ioctl)?;
-                        if let Some(code) = self.cause.raw_os_error() {
-                            write!(f, " with error code {code}")?;
-                        }
-                        write!(f, " ({})", self.cause.kind())
-                    }
-                }
-                impl Error for WrappedError {
-                    fn source(&self) -> Option<&(dyn Error + 'static)> {
-                        Some(&self.cause)
-                    }
-                }
+    /// Executes an `ioctl` that was added in Linux version `since`.
+    ///
+    /// On Linux, an `ENOTTY` error is turned into an [`io::ErrorKind::Unsupported`] error naming
+    /// the required version. All other errors are passed through like in [`Evdev::ioctl`].
+    unsafe fn ioctl_since<T>(
+        &self,
+        name: &'static str,
+        since: &'static str,
+        ioctl: Ioctl<T>,
+        arg: T,
+    ) -> io::Result<c_int> {
+        self.check_revoked()?;
+        match unsafe { ioctl.ioctl(self, arg) } {
+            Ok(ok) => Ok(ok),
+            Err(e) if cfg!(target_os = "linux") && e.raw_os_error() == Some(libc::ENOTTY) => {
+                Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    format!(
+                        "ioctl {name} is not supported by this kernel (requires Linux {since} or newer)"
+                    ),
+                ))
+            }
+            Err(e) => Err(wrap_ioctl_error(name, e)),
+        }
+    }
 
-                if e.raw_os_error() == Some(libc::ENODEV) {
-                    // `ENODEV` currently has no corresponding `ErrorKind` variant, so wrapping the
-                    // error would make it difficult to detect unplugged devices.
-                    // So instead we just return the original error.
-                    // https://github.com/rust-lang/rust/issues/130193
-                    Err(e)
-                } else {
-                    // Wrap the original I/O error in `WrappedError` to include additional
-                    // information.
-                    Err(io::Error::new(
-                        e.kind(),
-                        WrappedError {
-                            cause: e,
-                            ioctl: name,
-                        },
-                    ))
-                }
+    /// Returns an error if access was revoked via [`Evdev::revoke`].
+    ///
+    /// The kernel would fail with `ENODEV`, which is indistinguishable from the device being
+    /// unplugged, so we return a more descriptive error (with `ENODEV` as its source).
+    fn check_revoked(&self) -> io::Result<()> {
+        #[derive(Debug)]
+        struct RevokedError(io::Error);
+
+        impl fmt::Display for RevokedError {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("device access has been revoked via `Evdev::revoke`")
             }
         }
+        impl Error for RevokedError {
+            fn source(&self) -> Option<&(dyn Error + 'static)> {
+                Some(&self.0)
+            }
+        }
+
+        if self.is_revoked() {
+            Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                RevokedError(io::Error::from_raw_os_error(libc::ENODEV)),
+            ))
+        } else {
+            Ok(())
+        }
     }
 
     unsafe fn fetch_string(
         &self,
         ioctl_name: &'static str,
         ioctl: fn(usize) -> Ioctl<*mut c_char>,
+    ) -> io::Result<String> {
+        unsafe { self.fetch_string_with(ioctl_name, ioctl, &mut Vec::new()) }
+    }
+
+    /// Like [`Evdev::fetch_string`], but uses `buf` as the scratch buffer.
+    ///
+    /// `buf` is grown as needed and can be reused for fetching several strings.
+    unsafe fn fetch_string_with(
+        &self,
+        ioctl_name: &'static str,
+        ioctl: fn(usize) -> Ioctl<*mut c_char>,
+        buf: &mut Vec<u8>,
     ) -> io::Result<String> {
         // "fetch string" ioctls will return the number of bytes they've copied into our buffer.
         // This will be at most the length of the buffer. If that happens, some bytes might be lost,
         // so we retry the call after doubling the buffer size.
 
         const INITIAL_LEN: usize = 64;
-        let mut buf = vec![0_u8; INITIAL_LEN];
+        if buf.len() < INITIAL_LEN {
+            buf.resize(INITIAL_LEN, 0);
+        }
         let len = loop {
             let len = unsafe {
                 self.ioctl(
@@ -362,11 +706,11 @@ impl Evdev {
         };
 
         // `len` includes the trailing 0 byte
-        buf.truncate(len.saturating_sub(1) as usize);
+        let bytes = &buf[..len.saturating_sub(1) as usize];
 
         let string =
-            String::from_utf8(buf).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
-        Ok(string)
+            str::from_utf8(bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        Ok(string.to_owned())
     }
 
     unsafe fn fetch_bits<V: BitValue>(
@@ -397,13 +741,28 @@ impl Evdev {
     }
 
     /// Fetches device hardware information as an [`InputId`].
+    ///
+    /// The [`InputId`] of a device never changes, so it is only fetched once and cached afterwards.
     #[doc(alias = "EVIOCGID")]
     pub fn input_id(&self) -> io::Result<InputId> {
+        if let Some(id) = self.input_id.get() {
+            return Ok(*id);
+        }
+
         let mut out = MaybeUninit::uninit();
-        unsafe {
+        let id = unsafe {
             self.ioctl("EVIOCGID", EVIOCGID, out.as_mut_ptr())?;
-            Ok(InputId(out.assume_init()))
-        }
+            InputId(out.assume_init())
+        };
+        Ok(*self.input_id.get_or_init(|| id))
+    }
+
+    /// Returns the device's vendor and product ID in the `vvvv:pppp` hex format used by `lsusb`
+    /// and other tools (for example, `046d:c52b`).
+    ///
+    /// See [`InputId::vendor_product_string`].
+    pub fn vendor_product_string(&self) -> io::Result<String> {
+        Ok(self.input_id()?.vendor_product_string())
     }
 
     /// Fetches the device name.
@@ -425,13 +784,7 @@ impl Evdev {
     /// which can be done via [`Builder::with_phys`][crate::uinput::Builder::with_phys].
     #[doc(alias = "EVIOCGPHYS")]
     pub fn phys(&self) -> io::Result<Option<String>> {
-        unsafe {
-            match self.fetch_string("EVIOCGPHYS", EVIOCGPHYS) {
-                Ok(loc) => Ok(Some(loc)),
-                Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
-                Err(e) => Err(e),
-            }
-        }
+        unsafe { optional_string(self.fetch_string("EVIOCGPHYS", EVIOCGPHYS)) }
     }
 
     /// Fetches the unique identifier of this device.
@@ -440,12 +793,29 @@ impl Evdev {
     /// the empty string (rather than [`None`]).
     #[doc(alias = "EVIOCGUNIQ")]
     pub fn unique_id(&self) -> io::Result<Option<String>> {
+        unsafe { optional_string(self.fetch_string("EVIOCGUNIQ", EVIOCGUNIQ)) }
+    }
+
+    /// Fetches the device's [`InputId`], name, physical location, unique ID, and the evdev
+    /// subsystem version at once.
+    ///
+    /// This returns the same information as [`Evdev::input_id`], [`Evdev::name`],
+    /// [`Evdev::phys`], [`Evdev::unique_id`], and [`Evdev::driver_version`], but reuses a single
+    /// buffer for fetching all strings, which makes it cheaper when listing many devices.
+    pub fn metadata(&self) -> io::Result<DeviceMetadata> {
+        let mut buf = Vec::new();
         unsafe {
-            match self.fetch_string("EVIOCGUNIQ", EVIOCGUNIQ) {
-                Ok(loc) => Ok(Some(loc)),
-                Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
-                Err(e) => Err(e),
-            }
+            Ok(DeviceMetadata {
+                input_id: self.input_id()?,
+                name: self.fetch_string_with("EVIOCGNAME", EVIOCGNAME, &mut buf)?,
+                phys: optional_string(self.fetch_string_with("EVIOCGPHYS", EVIOCGPHYS, &mut buf))?,
+                unique_id: optional_string(self.fetch_string_with(
+                    "EVIOCGUNIQ",
+                    EVIOCGUNIQ,
+                    &mut buf,
+                ))?,
+                driver_version: self.driver_version()?,
+            })
         }
     }
 
@@ -466,11 +836,42 @@ impl Evdev {
         unsafe { self.fetch_bits("EVIOCGBIT", |len| EVIOCGBIT(0, len)) }
     }
 
+    /// Returns whether the device supports events of type `ty`.
+    ///
+    /// This is a shorthand for checking whether [`Evdev::supported_events`] contains `ty`, except
+    /// that [`EventType::SYN`] is always considered supported (see the platform-specific behavior
+    /// of [`Evdev::supported_events`]).
+    ///
+    /// The set of supported event types is not cached, so applications that check many types
+    /// should call [`Evdev::supported_events`] once instead.
+    pub fn has_event_type(&self, ty: EventType) -> io::Result<bool> {
+        if ty == EventType::SYN {
+            return Ok(true);
+        }
+        Ok(self.supported_events()?.contains(ty))
+    }
+
     /// Returns the set of supported [`Key`]s.
+    ///
+    /// This returns an empty set both for devices that don't support [`EventType::KEY`] at all,
+    /// and for devices that support it, but don't advertise any keys.
+    /// Use [`Evdev::key_capabilities`] to tell those cases apart.
     pub fn supported_keys(&self) -> io::Result<BitSet<Key>> {
         unsafe { self.fetch_bits("EVIOCGBIT", |len| EVIOCGBIT(EventType::KEY.0 as u8, len)) }
     }
 
+    /// Returns the set of supported [`Key`]s, or [`None`] if the device doesn't support
+    /// [`EventType::KEY`].
+    ///
+    /// Unlike [`Evdev::supported_keys`], this distinguishes devices that aren't key devices from
+    /// key devices that currently advertise no keys (which yield `Some` empty set).
+    pub fn key_capabilities(&self) -> io::Result<Option<BitSet<Key>>> {
+        if !self.has_event_type(EventType::KEY)? {
+            return Ok(None);
+        }
+        self.supported_keys().map(Some)
+    }
+
     /// Returns the set of supported [`Switch`]es.
     pub fn supported_switches(&self) -> io::Result<BitSet<Switch>> {
         unsafe { self.fetch_bits("EVIOCGBIT", |len| EVIOCGBIT(EventType::SW.0 as u8, len)) }
@@ -496,14 +897,67 @@ impl Evdev {
         unsafe { self.fetch_bits("EVIOCGBIT", |len| EVIOCGBIT(EventType::REL.0 as u8, len)) }
     }
 
+    /// Returns whether the device supports the relative axis `rel`.
+    ///
+    /// Like [`Evdev::has_event_type`], this is not cached, so applications that check many axes
+    /// should call [`Evdev::supported_rel_axes`] once instead.
+    pub fn has_rel_axis(&self, rel: Rel) -> io::Result<bool> {
+        Ok(self.supported_rel_axes()?.contains(rel))
+    }
+
+    /// Returns which evdev features are supported on the current platform.
+    ///
+    /// This is like [`BackendCaps::current`], but additionally checks whether the running kernel
+    /// supports event masks, which were added in Linux 4.4.
+    pub fn backend_caps(&self) -> BackendCaps {
+        let caps = BackendCaps::current();
+        if caps.event_masks() {
+            if let Err(e) = self.event_mask() {
+                if e.kind() == io::ErrorKind::Unsupported {
+                    return caps.without_event_masks();
+                }
+            }
+        }
+        caps
+    }
+
+    /// Returns the pointer-related capabilities of the device.
+    ///
+    /// This is derived from [`Evdev::supported_rel_axes`], and tells mouse-like devices apart by
+    /// the motion and scroll axes they support.
+    pub fn pointer_caps(&self) -> io::Result<PointerCaps> {
+        self.supported_rel_axes().map(PointerCaps::new)
+    }
+
     /// Returns the set of supported [`Abs`] axes.
     pub fn supported_abs_axes(&self) -> io::Result<BitSet<Abs>> {
         unsafe { self.fetch_bits("EVIOCGBIT", |len| EVIOCGBIT(EventType::ABS.0 as u8, len)) }
     }
 
+    /// Returns the set of supported [`Abs`] axes, excluding the multitouch axes.
+    ///
+    /// This returns only the axes below [`Abs::MT_SLOT`], which are the "regular" axes of the
+    /// device (like sticks, triggers, and pen/touchpad coordinates).
+    pub fn supported_plain_abs_axes(&self) -> io::Result<BitSet<Abs>> {
+        Ok(self
+            .supported_abs_axes()?
+            .iter()
+            .filter(|abs| abs.raw() < Abs::MT_SLOT.raw())
+            .collect())
+    }
+
     /// Returns the set of supported force-feedback [`Feature`][ff::Feature]s.
+    ///
+    /// The force-feedback features of a device never change, so they are only fetched once and
+    /// cached afterwards.
     pub fn supported_ff_features(&self) -> io::Result<BitSet<ff::Feature>> {
-        unsafe { self.fetch_bits("EVIOCGBIT", |len| EVIOCGBIT(EventType::FF.0 as u8, len)) }
+        if let Some(features) = self.ff_features.get() {
+            return Ok(*features);
+        }
+
+        let features =
+            unsafe { self.fetch_bits("EVIOCGBIT", |len| EVIOCGBIT(EventType::FF.0 as u8, len))? };
+        Ok(*self.ff_features.get_or_init(|| features))
     }
 
     /// Returns the number of force-feedback effects the device can store at the same time.
@@ -547,6 +1001,8 @@ impl Evdev {
     ///
     /// This method should generally not be used by applications, as it modifies globally visible
     /// device properties and can lead to the device not working correctly.
+    /// [`Evdev::try_set_abs_info`] performs additional checks that guard against some common
+    /// mistakes.
     #[doc(alias = "EVIOCSABS")]
     pub fn set_abs_info(&self, abs: Abs, info: AbsInfo) -> io::Result<()> {
         if abs.0 > Abs::MAX.0 {
@@ -562,6 +1018,104 @@ impl Evdev {
         Ok(())
     }
 
+    /// Applies a profile of [`AbsInfo`] settings to the device.
+    ///
+    /// This calls [`Evdev::set_abs_info`] for every [`AbsSetup`] in `setups`, in order, and can be
+    /// used to restore a previously saved calibration.
+    ///
+    /// # Errors
+    ///
+    /// Stops at the first axis that fails to apply, and returns an error that names that axis.
+    /// Axes preceding the failing one will already have been modified.
+    pub fn apply_abs_profile(&self, setups: impl IntoIterator<Item = AbsSetup>) -> io::Result<()> {
+        for setup in setups {
+            let abs = setup.abs();
+            self.set_abs_info(abs, *setup.abs_info()).map_err(|e| {
+                io::Error::new(e.kind(), format!("failed to apply profile to {abs:?}: {e}"))
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Sets the [`AbsInfo`] data associated with absolute axis `abs`, after validating it.
+    ///
+    /// This works like [`Evdev::set_abs_info`], but refuses to perform changes that are likely
+    /// to break the device or other applications reading from it.
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error of type [`io::ErrorKind::InvalidInput`] if:
+    ///
+    /// - `info` has a minimum that is larger than its maximum.
+    /// - `abs` is [`Abs::MT_SLOT`] (the number of multitouch slots cannot be changed).
+    ///
+    /// If `abs` is a multitouch axis (`ABS_MT_*`) and any multitouch slot is currently in use,
+    /// an error of type [`io::ErrorKind::ResourceBusy`] will be returned, since changing the
+    /// axis would desynchronize any [`EventReader`] that is tracking the active contacts.
+    pub fn try_set_abs_info(&self, abs: Abs, info: AbsInfo) -> io::Result<()> {
+        if info.minimum() > info.maximum() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "minimum of {:?} exceeds its maximum ({} > {})",
+                    abs,
+                    info.minimum(),
+                    info.maximum()
+                ),
+            ));
+        }
+        if abs == Abs::MT_SLOT {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "the number of multitouch slots cannot be changed",
+            ));
+        }
+        if abs.raw() > Abs::MT_SLOT.raw()
+            && abs.raw() <= Abs::MAX.raw()
+            && self.any_mt_slot_active()?
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::ResourceBusy,
+                format!("cannot modify {abs:?} while multitouch slots are in use"),
+            ));
+        }
+
+        self.set_abs_info(abs, info)
+    }
+
+    /// Returns whether any multitouch slot currently has a valid tracking ID assigned.
+    fn any_mt_slot_active(&self) -> io::Result<bool> {
+        let axes = self.supported_abs_axes()?;
+        if !axes.contains(Abs::MT_SLOT) || !axes.contains(Abs::MT_TRACKING_ID) {
+            return Ok(false);
+        }
+
+        let slots = self.abs_info(Abs::MT_SLOT)?.maximum().saturating_add(1);
+        let slots = slots.clamp(0, MAX_MT_SLOTS) as usize;
+        let mut buf = vec![0i32; slots + 1];
+        buf[0] = Abs::MT_TRACKING_ID.raw().into();
+        unsafe {
+            self.ioctl(
+                "EVIOCGMTSLOTS",
+                EVIOCGMTSLOTS(buf.len() * 4),
+                buf.as_mut_ptr().cast(),
+            )?;
+        }
+        Ok(buf[1..].iter().any(|&id| id >= 0))
+    }
+
+    /// Fetches the current state of all multitouch slots.
+    ///
+    /// If the device doesn't support multitouch slots (it doesn't advertise [`Abs::MT_SLOT`] and
+    /// [`Abs::MT_TRACKING_ID`]), the returned [`SlotSnapshot`] will not contain any slots.
+    ///
+    /// [`EventReader`] keeps track of the multitouch state itself, so when using one,
+    /// [`EventReader::slot_snapshot`] does the same without performing any system calls.
+    #[doc(alias = "EVIOCGMTSLOTS")]
+    pub fn mt_slot_state(&self) -> io::Result<SlotSnapshot> {
+        SlotSnapshot::current(self)
+    }
+
     /// Grabs this input device, making its events unavailable to other programs.
     ///
     /// This can be undone by calling [`Evdev::ungrab`]. The kernel will automatically *ungrab* a
@@ -572,12 +1126,16 @@ impl Evdev {
     /// This will return an error of type [`io::ErrorKind::ResourceBusy`] if the device is already
     /// grabbed by an application (including *this* application; in other words, calling `grab()`
     /// twice in a row will error).
+    ///
+    /// If the [`Evdev`] and all of its clones (see [`Evdev::try_clone`]) are dropped while the
+    /// device is still grabbed through them, a warning is logged to help diagnose forgotten grabs.
     #[doc(alias = "EVIOCGRAB")]
     pub fn grab(&self) -> io::Result<()> {
         unsafe {
             self.ioctl("EVIOCGRAB", EVIOCGRAB, 1)?;
-            Ok(())
         }
+        self.grab.grabbed.store(true, Ordering::Relaxed);
+        Ok(())
     }
 
     /// Ungrabs this input device, making its events available to other programs again.
@@ -592,20 +1150,42 @@ impl Evdev {
     pub fn ungrab(&self) -> io::Result<()> {
         unsafe {
             self.ioctl("EVIOCGRAB", EVIOCGRAB, 0)?;
-            Ok(())
         }
+        self.grab.grabbed.store(false, Ordering::Relaxed);
+        Ok(())
     }
 
     /// Revokes device access from this [`Evdev`] handle.
     ///
     /// This prevents this handle from receiving any more input events, and makes writes and ioctls
     /// (including later calls to `revoke()`) fail with `ENODEV`.
+    ///
+    /// # Errors
+    ///
+    /// This functionality was added in Linux 3.12. Older kernels will make this method fail with an
+    /// [`io::ErrorKind::Unsupported`] error.
+    ///
+    /// After a successful call, [`Evdev::is_revoked`] returns `true`, and methods that would
+    /// perform an `ioctl` or write to the device fail with an [`io::ErrorKind::PermissionDenied`]
+    /// error instead of the kernel's `ENODEV` (which also indicates an unplugged device).
     #[doc(alias = "EVIOCREVOKE")]
     pub fn revoke(&self) -> io::Result<()> {
         unsafe {
-            self.ioctl("EVIOCREVOKE", EVIOCREVOKE, 0)?;
-            Ok(())
+            self.ioctl_since("EVIOCREVOKE", "3.12", EVIOCREVOKE, 0)?;
         }
+        self.revoked.store(true, Ordering::Relaxed);
+        // Revoking access also releases the grab.
+        self.grab.grabbed.store(false, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Returns whether access to the device was revoked via [`Evdev::revoke`].
+    ///
+    /// This is shared with handles created by [`Evdev::try_clone`], since they refer to the same
+    /// open file, but not with handles that opened the device separately.
+    #[inline]
+    pub fn is_revoked(&self) -> bool {
+        self.revoked.load(Ordering::Relaxed)
     }
 
     /// Queries the current autorepeat settings.
@@ -713,14 +1293,28 @@ impl Evdev {
     /// *every* application using that device.
     #[doc(alias = "EVIOCSKEYCODE_V2")]
     pub fn set_keymap_entry(&self, scancode: Scancode, keycode: Key) -> io::Result<()> {
+        self.set_keymap_entry_full(&KeymapEntry::new(scancode, keycode))
+    }
+
+    /// Writes a [`KeymapEntry`] to the device's keymap.
+    ///
+    /// The entry is written by scancode (like [`Evdev::set_keymap_entry`]); its
+    /// [`KeymapEntry::index`] is ignored.
+    /// This allows copying a keymap from one device to another, by reading every entry with
+    /// [`Evdev::keymap_entry_by_index`] and writing it to the other device with this method.
+    ///
+    /// Use with caution! The keymap is a global device property and changes to it will affect
+    /// *every* application using that device.
+    #[doc(alias = "EVIOCSKEYCODE_V2")]
+    pub fn set_keymap_entry_full(&self, entry: &KeymapEntry) -> io::Result<()> {
+        let mut entry = *entry;
+        // Entries read via `keymap_entry_by_index` have the by-index flag set.
+        entry.0.flags = 0;
+        entry.0.index = 0;
         unsafe {
-            let mut entry = KeymapEntry::zeroed();
-            entry.0.keycode = keycode.raw().into();
-            entry.0.len = scancode.len;
-            entry.0.scancode = scancode.bytes;
             self.ioctl("EVIOCSKEYCODE_V2", EVIOCSKEYCODE_V2, &entry.0)?;
-            Ok(())
         }
+        Ok(())
     }
 
     /// Sets a keymap entry by index.
@@ -771,6 +1365,12 @@ impl Evdev {
     /// This is the recommended way of receiving input events.
     /// The [`EventReader`] will automatically resynchronize with the kernel's view of the device
     /// when an event is lost due to overflow.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`io::ErrorKind::PermissionDenied`] error if the device was opened in write-only
+    /// mode (see [`Evdev::can_read`]), since the [`EventReader`] wouldn't be able to read any
+    /// events.
     pub fn into_reader(self) -> io::Result<EventReader> {
         EventReader::new(self)
     }
@@ -810,6 +1410,8 @@ impl Evdev {
     ///
     /// [`RawEvents`] can be used (correctly) if the user is only interested in events pertaining to
     /// relative axes ([`RelEvent`][crate::event::RelEvent]), since those have no state.
+    /// Users of this method have to handle `SYN_DROPPED` events themselves, which
+    /// [`OverflowCounter`][crate::reader::OverflowCounter] can help with.
     ///
     /// - If the device is in blocking mode, [`RawEvents::next`] will block until an event is
     ///   available.
@@ -837,12 +1439,87 @@ impl Evdev {
     /// - If the device is in non-blocking mode, this method will return an error of type
     ///   [`io::ErrorKind::WouldBlock`] when there are no events to read.
     ///
+    /// Like with [`Evdev::raw_events`], `SYN_DROPPED` events have to be handled by the caller (see
+    /// [`OverflowCounter`][crate::reader::OverflowCounter]).
+    ///
     /// **Note**: If this method is used while the device is wrapped in an [`EventReader`], the
     /// [`EventReader`] will miss events and go out of sync with the device state. Don't do that.
     pub fn read_events(&self, buf: &mut [InputEvent]) -> io::Result<usize> {
         read_raw(&self.file, buf)
     }
 
+    /// Reads a batch of raw events and invokes `f` with every report in it, without allocating.
+    ///
+    /// Events are read into a fixed-size buffer on the stack, and `f` is invoked with a slice of
+    /// that buffer for every report (a sequence of events ending in `SYN_REPORT` or
+    /// `SYN_DROPPED`).
+    /// If a report is split across reads, more events are read to complete it.
+    /// Returns the number of times `f` was invoked.
+    ///
+    /// This is intended for latency-critical applications that process a high volume of events.
+    /// Compared to [`EventReader::reports`], it never allocates or copies events into a queue, but
+    /// it also doesn't offer any of the [`EventReader`]'s functionality:
+    ///
+    /// - There is no automatic resynchronization, so `SYN_DROPPED` events have to be handled by
+    ///   the caller (see [`OverflowCounter`][crate::reader::OverflowCounter]).
+    /// - No device state is tracked.
+    /// - Reports that don't fit in the internal buffer will be passed to `f` in several pieces.
+    ///
+    /// In exchange, less work is done per report: [`EventReader::reports`] updates its device
+    /// state for every event, and copies its queue of buffered events whenever a previously
+    /// returned [`Report`][crate::reader::Report] is still alive when more events are read.
+    /// The `reports` benchmark (`cargo bench --bench reports`) compares both methods.
+    ///
+    /// - If the device is in blocking mode, this method will block until at least 1 event can be
+    ///   read.
+    /// - If the device is in non-blocking mode, this method will return an error of type
+    ///   [`io::ErrorKind::WouldBlock`] when there are no events to read.
+    ///
+    /// **Note**: If this method is used while the device is wrapped in an [`EventReader`], the
+    /// [`EventReader`] will miss events and go out of sync with the device state. Don't do that.
+    pub fn with_reports(&self, mut f: impl FnMut(&[InputEvent])) -> io::Result<usize> {
+        const BUF_SIZE: usize = 64;
+
+        let mut buf = [InputEvent::zeroed(); BUF_SIZE];
+        let mut len = 0;
+        let mut reports = 0;
+        loop {
+            match self.read_events(&mut buf[len..]) {
+                Ok(n) => len += n,
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock && len != 0 => {
+                    // The rest of the report isn't available, so pass on what we have.
+                    warn!("incomplete report at the end of the kernel buffer");
+                    f(&buf[..len]);
+                    return Ok(reports + 1);
+                }
+                Err(e) => return Err(e),
+            }
+
+            let mut start = 0;
+            for i in 0..len {
+                if report_or_dropped(&buf[i]) {
+                    f(&buf[start..=i]);
+                    reports += 1;
+                    start = i + 1;
+                }
+            }
+
+            if start == 0 && len == BUF_SIZE {
+                // The report doesn't fit in the buffer, so pass the part we have.
+                f(&buf[..len]);
+                reports += 1;
+                len = 0;
+                continue;
+            }
+
+            buf.copy_within(start..len, 0);
+            len -= start;
+            if len == 0 {
+                return Ok(reports);
+            }
+        }
+    }
+
     /// Uploads or modifies a force-feedback effect.
     ///
     /// This is always a blocking operation, even if the [`Evdev`] is in non-blocking mode.
@@ -863,16 +1540,80 @@ impl Evdev {
         self.upload_ff_effect_impl(effect.into())
     }
     fn upload_ff_effect_impl(&self, mut effect: ff::Effect<'_>) -> io::Result<ff::EffectId> {
+        let _span = device_span!(
+            "Evdev::upload_ff_effect",
+            self,
+            effect_type = ?effect.effect_type(),
+        );
         trace!("uploading FF effect: {:?}", effect);
-        let now = Instant::now();
         unsafe {
             self.ioctl("EVIOCSFF", EVIOCSFF, &mut effect.raw)?;
         }
-        debug!("upload_ff_effect: ioctl took {:?}", now.elapsed());
 
         Ok(ff::EffectId(effect.raw.id))
     }
 
+    /// Replaces an already uploaded force-feedback effect with `effect`.
+    ///
+    /// This sets the ID of `effect` to `id` (via [`ff::Effect::with_id`]) and uploads it, which
+    /// makes the device reconfigure the effect in place.
+    /// If the effect is currently playing, it keeps playing with the new parameters.
+    ///
+    /// All parameters of the uploaded effect are replaced, so the direction, trigger, and replay
+    /// settings that should be kept have to be copied from the original [`ff::Effect`]:
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use evdevil::{Evdev, ff::{Effect, Replay, Rumble}};
+    ///
+    /// let evdev = Evdev::open("/dev/input/event0")?;
+    /// let effect = Effect::from(Rumble::new(0x4000, 0x4000)).with_replay(Replay::new(1000, 0));
+    /// let id = evdev.upload_ff_effect(effect)?;
+    /// evdev.control_ff(id, true)?;
+    ///
+    /// // Increase the intensity, keeping the rest of the configuration.
+    /// let stronger = Effect::from(Rumble::new(0xffff, 0xffff))
+    ///     .with_direction(effect.direction())
+    ///     .with_trigger(effect.trigger())
+    ///     .with_replay(effect.replay());
+    /// evdev.update_ff_effect(id, stronger)?;
+    /// # std::io::Result::Ok(())
+    /// ```
+    pub fn update_ff_effect<'a>(
+        &self,
+        id: ff::EffectId,
+        effect: impl Into<ff::Effect<'a>>,
+    ) -> io::Result<()> {
+        let new_id = self.upload_ff_effect_impl(effect.into().with_id(id))?;
+        debug_assert_eq!(new_id, id, "kernel changed the ID of an updated effect");
+        Ok(())
+    }
+
+    /// Uploads a force-feedback effect and returns an [`EffectHandle`] that erases it when dropped.
+    ///
+    /// This works like [`Evdev::upload_ff_effect`], but ties the lifetime of the effect in device
+    /// memory to the returned [`EffectHandle`], so that it cannot be leaked by forgetting to call
+    /// [`Evdev::erase_ff_effect`].
+    ///
+    /// ```no_run
+    /// use evdevil::{Evdev, ff::{Effect, Replay, Rumble}};
+    ///
+    /// let evdev = Evdev::open("/dev/input/event0")?;
+    /// let effect = Effect::from(Rumble::new(0x4000, 0x4000)).with_replay(Replay::new(1000, 0));
+    /// let handle = evdev.upload_ff_effect_guard(effect)?;
+    /// handle.play()?;
+    /// // ...
+    /// drop(handle); // erases the effect
+    /// # std::io::Result::Ok(())
+    /// ```
+    pub fn upload_ff_effect_guard<'a>(
+        &self,
+        effect: impl Into<ff::Effect<'a>>,
+    ) -> io::Result<EffectHandle<'_>> {
+        let id = self.upload_ff_effect_impl(effect.into())?;
+        Ok(EffectHandle { evdev: self, id })
+    }
+
     /// Deletes a previously uploaded force-feedback effect.
     #[doc(alias = "EVIOCRMFF")]
     pub fn erase_ff_effect(&self, id: ff::EffectId) -> io::Result<()> {
@@ -892,6 +1633,23 @@ impl Evdev {
         self.write_events(&[LedEvent::new(led, on).into()])
     }
 
+    /// Sets the state of a device LED, and checks whether that changed the LED's state.
+    ///
+    /// This works like [`Evdev::set_led`], but additionally queries [`Evdev::led_state`] before and
+    /// after writing the [`LedEvent`].
+    /// Returns `true` if the LED was switched to the requested state, and `false` if it already
+    /// was in that state, or if the write had no effect (for example, because the device doesn't
+    /// support `led`).
+    ///
+    /// The verification is best-effort: another client may change the LED's state in between the
+    /// queries and the write, in which case the result may not reflect what this call did.
+    pub fn set_led_verified(&self, led: Led, on: bool) -> io::Result<bool> {
+        let before = self.led_state()?.contains(led);
+        self.set_led(led, on)?;
+        let after = self.led_state()?.contains(led);
+        Ok(before != on && after == on)
+    }
+
     /// Starts or stops a force-feedback effect (eg. [`ff::Rumble`]).
     ///
     /// Before an effect can be started with this method, it needs to be uploaded via
@@ -903,6 +1661,32 @@ impl Evdev {
         self.write_events(&[ForceFeedbackEvent::control_effect(effect, active).into()])
     }
 
+    /// Uploads an [`ff::Rumble`] effect that plays for `duration`, and starts it immediately.
+    ///
+    /// `strong` and `weak` are the magnitudes of the strong and weak rumble motors.
+    /// `duration` is rounded down to milliseconds, and must not exceed 65535 ms.
+    ///
+    /// Devices that don't support [`ff::Feature::RUMBLE`], but do support
+    /// [`ff::Feature::PERIODIC`], will have the effect converted to a [`ff::Periodic`] sine wave by
+    /// the kernel.
+    ///
+    /// The effect stays in device memory after it has finished playing.
+    /// Callers should remove it via [`Evdev::erase_ff_effect`] once it is no longer needed, or
+    /// reuse it via [`Evdev::control_ff`].
+    pub fn rumble(&self, strong: u16, weak: u16, duration: Duration) -> io::Result<ff::EffectId> {
+        let length = u16::try_from(duration.as_millis()).map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("rumble duration {duration:?} exceeds the maximum of 65535 ms"),
+            )
+        })?;
+        let effect =
+            ff::Effect::from(ff::Rumble::new(strong, weak)).with_replay(ff::Replay::new(length, 0));
+        let id = self.upload_ff_effect(effect)?;
+        self.control_ff(id, true)?;
+        Ok(id)
+    }
+
     /// Sets the global gain for force-feedback effects.
     ///
     /// The `gain` value encodes the gain as a fraction of 65535 (100%).
@@ -912,7 +1696,21 @@ impl Evdev {
     /// This is a convenience wrapper around [`Evdev::write_events`] that sends a [`ForceFeedbackEvent`]
     /// to the device.
     pub fn set_ff_gain(&self, gain: u16) -> io::Result<()> {
-        self.write_events(&[ForceFeedbackEvent::control_gain(gain).into()])
+        self.write_events(&[ForceFeedbackEvent::control_gain(gain).into()])?;
+        self.ff_settings.gain.store(gain.into(), Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Returns the force-feedback gain last set via [`Evdev::set_ff_gain`].
+    ///
+    /// The kernel provides no way to read back the gain, so this value is **not** authoritative:
+    /// it is tracked by this [`Evdev`] handle (and shared with its clones created by
+    /// [`Evdev::try_clone`]), and does not reflect changes made by other processes, other
+    /// handles, or by writing raw events.
+    ///
+    /// Returns [`None`] if the gain hasn't been set through this handle or its clones.
+    pub fn ff_gain(&self) -> Option<u16> {
+        FfSettings::get(&self.ff_settings.gain)
     }
 
     /// Controls the autocenter feature for force-feedback effects.
@@ -924,7 +1722,52 @@ impl Evdev {
     /// This is a convenience wrapper around [`Evdev::write_events`] that sends a [`ForceFeedbackEvent`]
     /// to the device.
     pub fn set_ff_autocenter(&self, autocenter: u16) -> io::Result<()> {
-        self.write_events(&[ForceFeedbackEvent::control_autocenter(autocenter).into()])
+        self.write_events(&[ForceFeedbackEvent::control_autocenter(autocenter).into()])?;
+        self.ff_settings
+            .autocenter
+            .store(autocenter.into(), Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Returns the force-feedback autocenter power last set via [`Evdev::set_ff_autocenter`].
+    ///
+    /// Like [`Evdev::ff_gain`], this is tracked by the [`Evdev`] handle, since the kernel provides
+    /// no way to read back the value, so it is not authoritative.
+    ///
+    /// Returns [`None`] if the autocenter power hasn't been set through this handle or its clones.
+    pub fn ff_autocenter(&self) -> Option<u16> {
+        FfSettings::get(&self.ff_settings.autocenter)
+    }
+
+    /// Sets the global gain for force-feedback effects, after checking that the device supports it.
+    ///
+    /// Unlike [`Evdev::set_ff_gain`], this will first check whether the device supports
+    /// [`ff::Feature::GAIN`], and return an [`io::ErrorKind::Unsupported`] error if it doesn't.
+    pub fn try_set_ff_gain(&self, gain: u16) -> io::Result<()> {
+        self.require_ff_feature(ff::Feature::GAIN)?;
+        self.set_ff_gain(gain)
+    }
+
+    /// Controls the autocenter feature for force-feedback effects, after checking that the device
+    /// supports it.
+    ///
+    /// Unlike [`Evdev::set_ff_autocenter`], this will first check whether the device supports
+    /// [`ff::Feature::AUTOCENTER`], and return an [`io::ErrorKind::Unsupported`] error if it
+    /// doesn't.
+    pub fn try_set_ff_autocenter(&self, autocenter: u16) -> io::Result<()> {
+        self.require_ff_feature(ff::Feature::AUTOCENTER)?;
+        self.set_ff_autocenter(autocenter)
+    }
+
+    fn require_ff_feature(&self, feature: ff::Feature) -> io::Result<()> {
+        if self.supported_ff_features()?.contains(feature) {
+            Ok(())
+        } else {
+            Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                format!("device does not support force-feedback feature {feature:?}"),
+            ))
+        }
     }
 
     /// Writes events to the device.
@@ -943,10 +1786,63 @@ impl Evdev {
     ///
     /// If the [`Evdev`] does not have write permission, this method will fail with a
     /// [`io::ErrorKind::PermissionDenied`] error.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, this panics if `events` contains an [`EventType::UINPUT`] event or a
+    /// `SYN_DROPPED` event, since writing those to a device is always a bug.
     pub fn write_events(&self, events: &[InputEvent]) -> io::Result<()> {
+        self.check_revoked()?;
         write_raw(&self.file, events)
     }
 
+    /// Writes all events yielded by `events` to the device.
+    ///
+    /// This works like [`Evdev::write_events`], but doesn't require the caller to collect the
+    /// events into a slice first.
+    /// Events are collected into a small buffer on the stack, which is written to the device when
+    /// it runs full.
+    /// To avoid splitting reports across multiple writes, only the events up to the last
+    /// `SYN_REPORT` in the buffer are written at that point (unless the buffer contains no
+    /// `SYN_REPORT` at all).
+    ///
+    /// The same considerations as for [`Evdev::write_events`] apply.
+    pub fn write_iter(&self, events: impl IntoIterator<Item = InputEvent>) -> io::Result<()> {
+        const BUF_SIZE: usize = 64;
+
+        let mut buf = [InputEvent::zeroed(); BUF_SIZE];
+        let mut len = 0;
+        for ev in events {
+            if len == BUF_SIZE {
+                let end = buf
+                    .iter()
+                    .rposition(report_or_dropped)
+                    .map_or(BUF_SIZE, |i| i + 1);
+                self.write_events(&buf[..end])?;
+                buf.copy_within(end.., 0);
+                len -= end;
+            }
+
+            buf[len] = ev;
+            len += 1;
+        }
+
+        if len != 0 {
+            self.write_events(&buf[..len])?;
+        }
+        Ok(())
+    }
+
+    /// Returns an [`EventWriter`] for writing a batch of events to the device.
+    ///
+    /// Call [`EventWriter::finish`] to write a `SYN_REPORT` event and end the event batch.
+    ///
+    /// The same considerations as for [`Evdev::write_events`] apply to using the
+    /// [`EventWriter`].
+    pub fn writer(&self) -> EventWriter<'_> {
+        EventWriter::new(&self.file)
+    }
+
     /// Sets the [`clockid_t`] to be used for event timestamps.
     ///
     /// `evdev` doesn't support *all* clocks. This method will fail with an
@@ -965,8 +1861,70 @@ impl Evdev {
     pub fn set_clockid(&self, clockid: clockid_t) -> io::Result<()> {
         unsafe {
             self.ioctl("EVIOCSCLOCKID", EVIOCSCLOCKID, &clockid)?;
-            Ok(())
         }
+        self.clockid.store(clockid, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Returns the current time of the clock this [`Evdev`] uses for event timestamps,
+    /// represented like the timestamps returned by [`InputEvent::time`].
+    ///
+    /// When [`Evdev::set_clockid`] has been used to select a different clock (like
+    /// [`libc::CLOCK_MONOTONIC`]), event timestamps can no longer be compared to
+    /// [`SystemTime::now`].
+    /// Instead, this method can be used to obtain a reference time that can be passed to
+    /// [`InputEvent::latency_from`].
+    ///
+    /// The kernel does not allow querying the clock of an `evdev` file descriptor, so this uses
+    /// the clock last selected via [`Evdev::set_clockid`] on this [`Evdev`] or one of its clones
+    /// ([`libc::CLOCK_REALTIME`] if it was never called).
+    /// If the clock was changed through another file descriptor referring to the same open file,
+    /// the result will be wrong.
+    #[doc(alias = "clock_gettime")]
+    pub fn clock_now(&self) -> io::Result<SystemTime> {
+        clock_now(self.clockid.load(Ordering::Relaxed))
+    }
+}
+
+/// Wraps an error returned by the `ioctl` called `name` to include the `ioctl` name.
+fn wrap_ioctl_error(name: &'static str, e: io::Error) -> io::Error {
+    #[derive(Debug)]
+    struct WrappedError {
+        cause: io::Error,
+        ioctl: &'static str,
+    }
+
+    impl fmt::Display for WrappedError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "ioctl {} failed", self.ioctl)?;
+            if let Some(code) = self.cause.raw_os_error() {
+                write!(f, " with error code {code}")?;
+            }
+            write!(f, " ({})", self.cause.kind())
+        }
+    }
+    impl Error for WrappedError {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            Some(&self.cause)
+        }
+    }
+
+    if e.raw_os_error() == Some(libc::ENODEV) {
+        // `ENODEV` currently has no corresponding `ErrorKind` variant, so wrapping the
+        // error would make it difficult to detect unplugged devices.
+        // So instead we just return the original error.
+        // https://github.com/rust-lang/rust/issues/130193
+        e
+    } else {
+        // Wrap the original I/O error in `WrappedError` to include additional
+        // information.
+        io::Error::new(
+            e.kind(),
+            WrappedError {
+                cause: e,
+                ioctl: name,
+            },
+        )
     }
 }
 
@@ -986,8 +1944,12 @@ impl Evdev {
 /// # Platform Support
 ///
 /// FreeBSD does not support these APIs, so they will return an error when used.
+/// [`Evdev::backend_caps`] can be used to check for support up front.
 /// Applications should degrade gracefully when that happens, since the consequence of not filtering
 /// events is merely a decrease in performance.
+///
+/// On Linux, event masks were added in Linux 4.4. Older kernels will make these methods fail with
+/// an [`io::ErrorKind::Unsupported`] error.
 impl Evdev {
     fn fetch_mask<V: BitValue>(&self, ty: EventType) -> io::Result<BitSet<V>> {
         let mut set = BitSet::<V>::new();
@@ -998,7 +1960,7 @@ impl Evdev {
                 codes_size: (words.len() * size_of::<Word>()) as u32,
                 codes_ptr: words.as_mut_ptr().expose_provenance() as u64,
             };
-            self.ioctl("EVIOCGMASK", EVIOCGMASK, &mut mask)?;
+            self.ioctl_since("EVIOCGMASK", "4.4", EVIOCGMASK, &mut mask)?;
         }
         Ok(set)
     }
@@ -1016,7 +1978,7 @@ impl Evdev {
                 codes_size: (words.len() * size_of::<Word>()) as u32,
                 codes_ptr: words.as_ptr().expose_provenance() as u64,
             };
-            self.ioctl("EVIOCSMASK", EVIOCSMASK, &mask)?;
+            self.ioctl_since("EVIOCSMASK", "4.4", EVIOCSMASK, &mask)?;
         }
         Ok(())
     }
@@ -1083,6 +2045,291 @@ impl Evdev {
     }
 }
 
+/// The access mode an [`Evdev`] was opened with.
+///
+/// Returned by [`Evdev::access_mode`] and [`Evdev::open_reporting`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AccessMode {
+    /// The handle can be used for everything.
+    ReadWrite,
+    /// The handle can receive events, but can't write events (like [`LedEvent`]s) or upload
+    /// force-feedback effects.
+    ReadOnly,
+    /// The handle can write events and upload force-feedback effects, but can't receive events.
+    WriteOnly,
+}
+
+/// Keeps an [`Evdev`] in non-blocking mode while alive.
+///
+/// Returned by [`Evdev::nonblocking_scope`].
+/// Dropping the guard restores the blocking mode the [`Evdev`] was in when the guard was created.
+#[derive(Debug)]
+pub struct NonblockingGuard<'a> {
+    evdev: &'a Evdev,
+    was_nonblocking: bool,
+}
+
+impl NonblockingGuard<'_> {
+    /// Returns whether the [`Evdev`] was already in non-blocking mode when the guard was created.
+    ///
+    /// If this is `true`, dropping the guard will not change the mode of the [`Evdev`].
+    #[inline]
+    pub fn was_nonblocking(&self) -> bool {
+        self.was_nonblocking
+    }
+}
+
+impl Deref for NonblockingGuard<'_> {
+    type Target = Evdev;
+
+    #[inline]
+    fn deref(&self) -> &Evdev {
+        self.evdev
+    }
+}
+
+impl Drop for NonblockingGuard<'_> {
+    fn drop(&mut self) {
+        if self.was_nonblocking {
+            // Avoid the syscall if the device was already in non-blocking mode.
+            return;
+        }
+
+        if let Err(e) = self.evdev.set_nonblocking(false) {
+            error!("failed to move evdev back into blocking mode: {e}");
+        }
+    }
+}
+
+/// A force-feedback effect in device memory that is erased when dropped.
+///
+/// Returned by [`Evdev::upload_ff_effect_guard`].
+///
+/// Errors encountered while erasing the effect on drop are logged and otherwise ignored.
+/// Use [`EffectHandle::erase`] to handle them.
+#[derive(Debug)]
+#[must_use = "dropping an `EffectHandle` erases the effect"]
+pub struct EffectHandle<'a> {
+    evdev: &'a Evdev,
+    id: ff::EffectId,
+}
+
+impl<'a> EffectHandle<'a> {
+    /// Returns the [`ff::EffectId`] the device assigned to the effect.
+    #[inline]
+    pub fn id(&self) -> ff::EffectId {
+        self.id
+    }
+
+    /// Returns the [`Evdev`] the effect was uploaded to.
+    #[inline]
+    pub fn evdev(&self) -> &'a Evdev {
+        self.evdev
+    }
+
+    /// Starts playing the effect.
+    ///
+    /// Shorthand for [`Evdev::control_ff`] with `active` set to `true`.
+    pub fn play(&self) -> io::Result<()> {
+        self.evdev.control_ff(self.id, true)
+    }
+
+    /// Stops playing the effect.
+    ///
+    /// Shorthand for [`Evdev::control_ff`] with `active` set to `false`.
+    pub fn stop(&self) -> io::Result<()> {
+        self.evdev.control_ff(self.id, false)
+    }
+
+    /// Erases the effect from device memory, returning any error that occurs.
+    pub fn erase(self) -> io::Result<()> {
+        let this = ManuallyDrop::new(self);
+        this.evdev.erase_ff_effect(this.id)
+    }
+
+    /// Releases the effect without erasing it, and returns its [`ff::EffectId`].
+    ///
+    /// The effect stays in device memory until it is erased via [`Evdev::erase_ff_effect`], or
+    /// until the device is closed.
+    pub fn into_id(self) -> ff::EffectId {
+        ManuallyDrop::new(self).id
+    }
+}
+
+impl Drop for EffectHandle<'_> {
+    fn drop(&mut self) {
+        if let Err(e) = self.evdev.erase_ff_effect(self.id) {
+            error!("uncaught error in `EffectHandle` destructor: {e}");
+        }
+    }
+}
+
+/// Force-feedback settings written through an [`Evdev`] handle.
+///
+/// Values above `u16::MAX` mean that the setting hasn't been written yet.
+#[derive(Debug)]
+struct FfSettings {
+    gain: AtomicU32,
+    autocenter: AtomicU32,
+}
+
+impl FfSettings {
+    const UNSET: u32 = u32::MAX;
+
+    fn new() -> Self {
+        Self {
+            gain: AtomicU32::new(Self::UNSET),
+            autocenter: AtomicU32::new(Self::UNSET),
+        }
+    }
+
+    fn get(value: &AtomicU32) -> Option<u16> {
+        u16::try_from(value.load(Ordering::Relaxed)).ok()
+    }
+}
+
+/// Tracks whether a device was grabbed through an [`Evdev`] handle (or one of its clones), and
+/// warns when the last of them is dropped without ungrabbing it.
+#[derive(Debug, Default)]
+struct GrabTracker {
+    grabbed: AtomicBool,
+}
+
+impl GrabTracker {
+    /// Disarms the warning, for when the file descriptor outlives the [`Evdev`].
+    fn forget(&self) {
+        self.grabbed.store(false, Ordering::Relaxed);
+    }
+}
+
+impl Drop for GrabTracker {
+    fn drop(&mut self) {
+        if *self.grabbed.get_mut() {
+            warn!("`Evdev` dropped while the device is still grabbed through it");
+        }
+    }
+}
+
+/// Returns whether `meta` describes an `evdev` device node.
+///
+/// This only looks at the metadata and sysfs, so the node doesn't have to be opened.
+#[cfg(target_os = "linux")]
+fn is_evdev_node(meta: &fs::Metadata) -> io::Result<bool> {
+    /// `INPUT_MAJOR` from `<linux/major.h>`.
+    const INPUT_MAJOR: u32 = 13;
+
+    if !meta.file_type().is_char_device() {
+        return Ok(false);
+    }
+    let (major, minor) = (libc::major(meta.rdev()), libc::minor(meta.rdev()));
+    if major != INPUT_MAJOR {
+        return Ok(false);
+    }
+
+    // joydev and mousedev nodes share the input major; only evdev nodes are named `eventN`.
+    match fs::read_link(format!("/sys/dev/char/{major}:{minor}")) {
+        Ok(link) => Ok(link
+            .file_name()
+            .is_some_and(|name| name.as_encoded_bytes().starts_with(b"event"))),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+/// Non-standard device paths are not supported on this platform.
+#[cfg(not(target_os = "linux"))]
+fn is_evdev_node(_meta: &fs::Metadata) -> io::Result<bool> {
+    Ok(false)
+}
+
+fn not_an_evdev_node(path: &Path) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!(
+            "evdev device path '{}' must match '/dev/input/event*' or refer to an evdev device node",
+            path.display()
+        ),
+    )
+}
+
+/// Maps the [`io::ErrorKind::NotFound`] error returned by string `ioctl`s for unset strings to
+/// [`None`].
+fn optional_string(res: io::Result<String>) -> io::Result<Option<String>> {
+    match res {
+        Ok(s) => Ok(Some(s)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Descriptive information about an input device.
+///
+/// Returned by [`Evdev::metadata`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeviceMetadata {
+    input_id: InputId,
+    name: String,
+    phys: Option<String>,
+    unique_id: Option<String>,
+    driver_version: Version,
+}
+
+impl DeviceMetadata {
+    /// Returns the device's hardware information.
+    ///
+    /// See [`Evdev::input_id`].
+    #[inline]
+    pub fn input_id(&self) -> InputId {
+        self.input_id
+    }
+
+    /// Returns the device name.
+    ///
+    /// See [`Evdev::name`].
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the physical location of the device, if it has one.
+    ///
+    /// See [`Evdev::phys`].
+    #[inline]
+    pub fn phys(&self) -> Option<&str> {
+        self.phys.as_deref()
+    }
+
+    /// Returns the unique identifier of the device, if it has one.
+    ///
+    /// See [`Evdev::unique_id`].
+    #[inline]
+    pub fn unique_id(&self) -> Option<&str> {
+        self.unique_id.as_deref()
+    }
+
+    /// Returns the evdev subsystem version.
+    ///
+    /// See [`Evdev::driver_version`].
+    #[inline]
+    pub fn driver_version(&self) -> Version {
+        self.driver_version
+    }
+}
+
+/// Identifies an input device.
+///
+/// Returned by [`Evdev::id`].
+///
+/// [`DeviceKey`] implements [`Hash`] and [`Eq`], so it can be used to look up per-device data in
+/// a [`HashMap`][std::collections::HashMap] or similar collection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DeviceKey {
+    /// The device containing the device node's file system entry.
+    dev: u64,
+    /// The device number of the `evdev` device itself.
+    rdev: u64,
+}
+
 /// Reads raw [`InputEvent`]s from an [`Evdev`].
 ///
 /// Returned by [`Evdev::raw_events`].
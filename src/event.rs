@@ -95,6 +95,7 @@ use std::fmt;
 use std::ops::Deref;
 use std::time::{Duration, SystemTime};
 
+use crate::bits::BitValue;
 use crate::ff::{self, EffectId};
 use crate::raw::input::input_event;
 
@@ -125,6 +126,14 @@ impl InputEvent {
         })
     }
 
+    /// Creates an [`InputEvent`] with the given fields, timestamped with the current time.
+    ///
+    /// See [`InputEvent::with_time_now`].
+    #[inline]
+    pub fn now(ty: EventType, raw_code: u16, raw_value: i32) -> Self {
+        Self::new(ty, raw_code, raw_value).with_time_now()
+    }
+
     /// Creates an [`InputEvent`] with all fields zeroed out.
     ///
     /// Useful as a dummy or filler value that will be overwritten with a "real" event soon.
@@ -167,6 +176,34 @@ impl InputEvent {
         self
     }
 
+    /// Shifts the timestamp of `self` from the time base `from` to the time base `to`.
+    ///
+    /// The returned event's timestamp has the same offset from `to` that `self` has from `from`.
+    /// This is useful for replaying recorded events: passing the time the recording was started as
+    /// `from`, and the time the replay was started as `to`, will preserve the relative timing of the
+    /// recorded events.
+    ///
+    /// Like [`InputEvent::with_time`], the result is truncated to microsecond resolution.
+    pub fn rebase(&self, from: SystemTime, to: SystemTime) -> Self {
+        let time = self.time();
+        let time = match time.duration_since(from) {
+            Ok(offset) => to + offset,
+            Err(e) => to - e.duration(),
+        };
+        self.with_time(time)
+    }
+
+    /// Changes the timestamp of `self` to the current time, as returned by [`SystemTime::now`].
+    ///
+    /// This matches the default clock used for event timestamps by the kernel (which can be
+    /// changed via [`Evdev::set_clockid`]).
+    ///
+    /// [`Evdev::set_clockid`]: crate::Evdev::set_clockid
+    #[inline]
+    pub fn with_time_now(self) -> Self {
+        self.with_time(SystemTime::now())
+    }
+
     /// Returns the timestamp stored in the event.
     ///
     /// The clock source used to generate event timestamps can be changed by calling
@@ -185,6 +222,34 @@ impl InputEvent {
             }
         }
     }
+
+    /// Returns how much time has passed between this event's timestamp and `reference`.
+    ///
+    /// Returns [`None`] if `reference` is earlier than the event's timestamp, or if the timestamp
+    /// is out of range of [`SystemTime`].
+    ///
+    /// **Note**: `reference` has to come from the same clock that the event timestamps are taken
+    /// from.
+    /// If a different clock has been selected with [`Evdev::set_clockid`] (for example,
+    /// `CLOCK_MONOTONIC`), event timestamps can not be compared to [`SystemTime::now`], and the
+    /// caller should use [`Evdev::clock_now`] to obtain `reference` instead.
+    ///
+    /// [`Evdev::set_clockid`]: crate::Evdev::set_clockid
+    /// [`Evdev::clock_now`]: crate::Evdev::clock_now
+    pub fn latency_from(&self, reference: SystemTime) -> Option<Duration> {
+        reference.duration_since(self.try_time()?).ok()
+    }
+
+    /// Returns how much time has passed since this event's timestamp.
+    ///
+    /// This is equivalent to `self.latency_from(SystemTime::now())`, and so is only meaningful if
+    /// the device uses the default real-time clock (see [`InputEvent::latency_from`]).
+    ///
+    /// Returns [`None`] if the event's timestamp lies in the future.
+    pub fn elapsed(&self) -> Option<Duration> {
+        self.latency_from(SystemTime::now())
+    }
+
     fn try_time(&self) -> Option<SystemTime> {
         let sec = self.0.time.tv_sec;
         let usec = self.0.time.tv_usec.clamp(0, 999_999);
@@ -209,6 +274,10 @@ impl InputEvent {
     /// [`EventKind`] is `#[non_exhaustive]`, so matching on it requires a wildcard arm that will
     /// catch any events that don't have a specific [`EventKind`] variant.
     /// Future versions of `evdevil` might add new variants to catch those events.
+    ///
+    /// This is a single `match` on the event type, so it is about as cheap as matching on
+    /// [`InputEvent::event_type`] directly.
+    /// The `kind` benchmark (`cargo bench --bench kind`) compares both.
     #[inline]
     pub fn kind(&self) -> EventKind {
         match self.event_type() {
@@ -228,6 +297,21 @@ impl InputEvent {
     }
 
     /// Returns the [`EventType`] of this event.
+    ///
+    /// The [`EventType`] constants can be used as `match` patterns, which is handy when only the
+    /// type of an event matters and the typed wrapper returned by [`InputEvent::kind`] isn't
+    /// needed:
+    ///
+    /// ```
+    /// use evdevil::event::{EventType, InputEvent, Key, KeyEvent, KeyState};
+    ///
+    /// let ev: InputEvent = KeyEvent::new(Key::KEY_A, KeyState::PRESSED).into();
+    /// let is_input = match ev.event_type() {
+    ///     EventType::KEY | EventType::REL | EventType::ABS => true,
+    ///     _ => false,
+    /// };
+    /// assert!(is_input);
+    /// ```
     #[inline]
     pub fn event_type(&self) -> EventType {
         EventType(self.0.type_)
@@ -413,8 +497,16 @@ impl From<Syn> for InputEvent {
 }
 
 impl KeyEvent {
+    /// Creates a [`KeyEvent`] that reports `key` as being in `state`.
+    ///
+    /// In debug builds, this panics if `key` exceeds [`<Key as BitValue>::MAX`][BitValue::MAX],
+    /// since the kernel would not accept such an event.
+    ///
+    /// `state` is not checked: uinput devices can emit arbitrary key values, and the kernel passes
+    /// them through to readers unchanged.
     #[inline]
     pub fn new(key: Key, state: KeyState) -> Self {
+        debug_assert!(key <= <Key as BitValue>::MAX, "{key:?} is out of range");
         Self(InputEvent::new(EventType::KEY, key.0, state.0))
     }
 
@@ -433,6 +525,15 @@ impl KeyEvent {
     pub fn state(&self) -> KeyState {
         KeyState(self.raw_value())
     }
+
+    /// Returns whether this is an autorepeat event ([`KeyState::REPEAT`]).
+    ///
+    /// Autorepeat events are generated while a key is held down, and are useful for text input,
+    /// but are usually unwanted for other purposes like game input.
+    #[inline]
+    pub fn is_repeat(&self) -> bool {
+        self.state() == KeyState::REPEAT
+    }
 }
 impl fmt::Debug for KeyEvent {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -448,6 +549,12 @@ ffi_enum! {
     /// State of a [`Key`], stored as the value of a [`KeyEvent`].
     ///
     /// Returned by [`KeyEvent::state`].
+    ///
+    /// The kernel only ever emits the values 0, 1, and 2 (the constants defined here), but
+    /// devices created via `uinput` can send arbitrary values.
+    /// Use [`KeyState::is_known`] to detect such values.
+    /// [`EventReader`][crate::EventReader] treats them like [`KeyState::REPEAT`] and does not
+    /// change the state of the key.
     pub enum KeyState: i32 {
         /// The key used to be pressed and has now been released.
         RELEASED = 0,
@@ -457,6 +564,16 @@ ffi_enum! {
         REPEAT = 2,
     }
 }
+impl KeyState {
+    /// Returns whether this is one of the known [`KeyState`] constants.
+    ///
+    /// Values for which this returns `false` are neither a press nor a release.
+    #[inline]
+    pub fn is_known(&self) -> bool {
+        self.variant_name().is_some()
+    }
+}
+
 impl fmt::Debug for KeyState {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self.variant_name() {
@@ -483,6 +600,34 @@ impl RelEvent {
     pub fn value(&self) -> i32 {
         self.raw_value()
     }
+
+    /// Interprets this event as a scroll wheel movement, and returns the scroll direction and
+    /// magnitude.
+    ///
+    /// This handles [`Rel::WHEEL`], [`Rel::HWHEEL`], and their high-resolution variants
+    /// [`Rel::WHEEL_HI_RES`] and [`Rel::HWHEEL_HI_RES`].
+    /// Positive [`Rel::WHEEL`] values scroll up (away from the user), and positive
+    /// [`Rel::HWHEEL`] values scroll right.
+    /// The magnitude is given in detents for the low-resolution axes, and in 1/120ths of a detent
+    /// for the high-resolution ones.
+    ///
+    /// Returns [`None`] for other axes, and for events with a value of 0.
+    ///
+    /// **Note**: Devices supporting high-resolution scrolling emit events for both the normal and
+    /// the high-resolution axis, so applications should only handle one of them.
+    pub fn scroll(&self) -> Option<(ScrollDirection, u32)> {
+        let (negative, positive) = match self.rel() {
+            Rel::WHEEL | Rel::WHEEL_HI_RES => (ScrollDirection::Down, ScrollDirection::Up),
+            Rel::HWHEEL | Rel::HWHEEL_HI_RES => (ScrollDirection::Left, ScrollDirection::Right),
+            _ => return None,
+        };
+        let direction = match self.value().signum() {
+            1 => positive,
+            -1 => negative,
+            _ => return None,
+        };
+        Some((direction, self.value().unsigned_abs()))
+    }
 }
 impl fmt::Debug for RelEvent {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -494,6 +639,21 @@ impl fmt::Debug for RelEvent {
     }
 }
 
+/// Direction of a scroll wheel movement.
+///
+/// Returned by [`RelEvent::scroll`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ScrollDirection {
+    /// Scrolling up, away from the user.
+    Up,
+    /// Scrolling down, towards the user.
+    Down,
+    /// Scrolling left.
+    Left,
+    /// Scrolling right.
+    Right,
+}
+
 impl AbsEvent {
     #[inline]
     pub fn new(abs: Abs, value: i32) -> Self {
@@ -521,8 +681,16 @@ impl fmt::Debug for AbsEvent {
 }
 
 impl SwitchEvent {
+    /// Creates a [`SwitchEvent`] that reports `switch` as being on or off.
+    ///
+    /// In debug builds, this panics if `switch` exceeds
+    /// [`<Switch as BitValue>::MAX`][BitValue::MAX].
     #[inline]
     pub fn new(switch: Switch, on: bool) -> Self {
+        debug_assert!(
+            switch <= <Switch as BitValue>::MAX,
+            "{switch:?} is out of range"
+        );
         Self(InputEvent::new(
             EventType::SW,
             switch.0,
@@ -573,8 +741,13 @@ impl fmt::Debug for MiscEvent {
 }
 
 impl LedEvent {
+    /// Creates a [`LedEvent`] that turns `led` on or off.
+    ///
+    /// In debug builds, this panics if `led` exceeds
+    /// [`<Led as BitValue>::MAX`][BitValue::MAX].
     #[inline]
     pub fn new(led: Led, on: bool) -> Self {
+        debug_assert!(led <= <Led as BitValue>::MAX, "{led:?} is out of range");
         Self(InputEvent::new(
             EventType::LED,
             led.0,
@@ -636,8 +809,16 @@ impl fmt::Debug for RepeatEvent {
 }
 
 impl SoundEvent {
+    /// Creates a [`SoundEvent`] that starts or stops playing `sound`.
+    ///
+    /// In debug builds, this panics if `sound` exceeds
+    /// [`<Sound as BitValue>::MAX`][BitValue::MAX].
     #[inline]
     pub fn new(sound: Sound, playing: bool) -> Self {
+        debug_assert!(
+            sound <= <Sound as BitValue>::MAX,
+            "{sound:?} is out of range"
+        );
         Self(InputEvent::new(
             EventType::SND,
             sound.0,
@@ -740,6 +921,47 @@ impl ForceFeedbackEvent {
             raw => ForceFeedbackCode::__Unknown(raw),
         }
     }
+
+    /// Returns the requested master gain, if this event sets the gain.
+    ///
+    /// The gain is encoded as a fraction of 65535.
+    ///
+    /// Returns [`None`] if the event code is not [`ForceFeedbackCode::SetGain`], or if the value
+    /// is out of range (the kernel ignores such events).
+    #[inline]
+    pub fn gain(&self) -> Option<u16> {
+        match self.code() {
+            ForceFeedbackCode::SetGain => u16::try_from(self.raw_value()).ok(),
+            _ => None,
+        }
+    }
+
+    /// Returns the requested autocenter strength, if this event sets it.
+    ///
+    /// The autocenter strength is encoded as a fraction of 65535.
+    ///
+    /// Returns [`None`] if the event code is not [`ForceFeedbackCode::SetAutocenter`], or if the
+    /// value is out of range (the kernel ignores such events).
+    #[inline]
+    pub fn autocenter(&self) -> Option<u16> {
+        match self.code() {
+            ForceFeedbackCode::SetAutocenter => u16::try_from(self.raw_value()).ok(),
+            _ => None,
+        }
+    }
+
+    /// Returns whether the effect should be started or stopped, if this event controls an effect.
+    ///
+    /// The affected effect can be retrieved via [`ForceFeedbackEvent::code`].
+    ///
+    /// Returns [`None`] if the event code is not [`ForceFeedbackCode::ControlEffect`].
+    #[inline]
+    pub fn effect_active(&self) -> Option<bool> {
+        match self.code() {
+            ForceFeedbackCode::ControlEffect(_) => Some(self.raw_value() != 0),
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Debug for ForceFeedbackEvent {
@@ -797,6 +1019,29 @@ ffi_enum! {
     }
 }
 
+impl MtToolType {
+    /// Returns whether this tool type represents an intentional contact ([`MtToolType::FINGER`] or
+    /// [`MtToolType::PEN`]).
+    ///
+    /// Touch handling that wants to ignore accidental contacts can skip slots for which this
+    /// returns `false`.
+    #[inline]
+    pub fn is_contact(&self) -> bool {
+        *self == Self::FINGER || *self == Self::PEN
+    }
+
+    /// Returns whether contacts with this tool type should be rejected as accidental.
+    ///
+    /// This is `true` for [`MtToolType::PALM`], which devices report when they detect a palm
+    /// resting on the surface.
+    /// Tool types that are neither contacts nor rejected (like [`MtToolType::DIAL`]) have their own
+    /// meaning.
+    #[inline]
+    pub fn is_rejected(&self) -> bool {
+        *self == Self::PALM
+    }
+}
+
 impl fmt::Debug for MtToolType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self.variant_name() {
@@ -810,6 +1055,141 @@ impl fmt::Debug for MtToolType {
 mod tests {
     use super::*;
 
+    #[test]
+    fn key_state_known() {
+        assert!(KeyState::RELEASED.is_known());
+        assert!(KeyState::PRESSED.is_known());
+        assert!(KeyState::REPEAT.is_known());
+        assert!(!KeyState(3).is_known());
+        assert!(!KeyState(-1).is_known());
+    }
+
+    #[test]
+    fn mt_tool_type_classification() {
+        assert!(MtToolType::FINGER.is_contact());
+        assert!(MtToolType::PEN.is_contact());
+        assert!(!MtToolType::PALM.is_contact());
+        assert!(MtToolType::PALM.is_rejected());
+        assert!(!MtToolType::FINGER.is_rejected());
+        assert!(!MtToolType::DIAL.is_contact());
+        assert!(!MtToolType::DIAL.is_rejected());
+        assert!(!MtToolType(0x7f).is_contact());
+    }
+
+    #[test]
+    fn scroll() {
+        let scroll = |rel, value| RelEvent::new(rel, value).scroll();
+        assert_eq!(scroll(Rel::WHEEL, 1), Some((ScrollDirection::Up, 1)));
+        assert_eq!(scroll(Rel::WHEEL, -2), Some((ScrollDirection::Down, 2)));
+        assert_eq!(
+            scroll(Rel::WHEEL_HI_RES, 60),
+            Some((ScrollDirection::Up, 60))
+        );
+        assert_eq!(scroll(Rel::HWHEEL, 1), Some((ScrollDirection::Right, 1)));
+        assert_eq!(
+            scroll(Rel::HWHEEL_HI_RES, -120),
+            Some((ScrollDirection::Left, 120))
+        );
+        assert_eq!(
+            scroll(Rel::WHEEL, i32::MIN),
+            Some((ScrollDirection::Down, 1 << 31))
+        );
+        assert_eq!(scroll(Rel::WHEEL, 0), None);
+        assert_eq!(scroll(Rel::X, 1), None);
+    }
+
+    #[test]
+    fn valid_wrappers() {
+        KeyEvent::new(<Key as BitValue>::MAX, KeyState::REPEAT);
+        SwitchEvent::new(<Switch as BitValue>::MAX, true);
+        LedEvent::new(<Led as BitValue>::MAX, true);
+        SoundEvent::new(<Sound as BitValue>::MAX, true);
+    }
+
+    #[test]
+    fn unknown_key_state() {
+        // Can be received from a device, so it has to be possible to construct (and forward) it.
+        let ev = KeyEvent::new(Key::KEY_A, KeyState(5));
+        assert_eq!(ev.state(), KeyState(5));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic = "is out of range"]
+    fn invalid_switch() {
+        SwitchEvent::new(Switch::from_raw(<Switch as BitValue>::MAX.raw() + 1), true);
+    }
+
+    #[test]
+    fn ff_event_values() {
+        let ev = ForceFeedbackEvent::control_gain(0x1234);
+        assert_eq!(ev.code(), ForceFeedbackCode::SetGain);
+        assert_eq!(ev.gain(), Some(0x1234));
+        assert_eq!(ev.autocenter(), None);
+        assert_eq!(ev.effect_active(), None);
+
+        let ev = ForceFeedbackEvent::control_autocenter(u16::MAX);
+        assert_eq!(ev.code(), ForceFeedbackCode::SetAutocenter);
+        assert_eq!(ev.gain(), None);
+        assert_eq!(ev.autocenter(), Some(u16::MAX));
+        assert_eq!(ev.effect_active(), None);
+
+        for active in [true, false] {
+            let ev = ForceFeedbackEvent::control_effect(EffectId(3), active);
+            assert_eq!(ev.code(), ForceFeedbackCode::ControlEffect(EffectId(3)));
+            assert_eq!(ev.gain(), None);
+            assert_eq!(ev.autocenter(), None);
+            assert_eq!(ev.effect_active(), Some(active));
+        }
+
+        let ev = ForceFeedbackEvent(InputEvent::new(EventType::FF, ff::Feature::GAIN.0, -1));
+        assert_eq!(ev.gain(), None);
+    }
+
+    #[test]
+    fn time_now() {
+        let before = SystemTime::now() - Duration::from_millis(1);
+        let ev = InputEvent::now(EventType::REL, Rel::DIAL.raw(), 1);
+        assert_eq!(ev.event_type(), EventType::REL);
+        assert_eq!(ev.raw_code(), Rel::DIAL.raw());
+        assert_eq!(ev.raw_value(), 1);
+        assert!(ev.time() >= before);
+        assert!(InputEvent::zeroed().with_time_now().time() >= before);
+    }
+
+    #[test]
+    fn rebase() {
+        let from = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000);
+        let to = SystemTime::UNIX_EPOCH + Duration::from_secs(5_000);
+        let ev = InputEvent::new(EventType::REL, Rel::DIAL.raw(), 1)
+            .with_time(from + Duration::from_millis(250));
+
+        let rebased = ev.rebase(from, to);
+        assert_eq!(rebased.time(), to + Duration::from_millis(250));
+        assert_eq!(rebased.event_type(), EventType::REL);
+        assert_eq!(rebased.raw_code(), Rel::DIAL.raw());
+        assert_eq!(rebased.raw_value(), 1);
+
+        let early = ev.rebase(from + Duration::from_secs(1), to);
+        assert_eq!(early.time(), to - Duration::from_millis(750));
+    }
+
+    #[test]
+    fn latency() {
+        let time = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000);
+        let ev = InputEvent::zeroed().with_time(time);
+        assert_eq!(
+            ev.latency_from(time + Duration::from_millis(5)),
+            Some(Duration::from_millis(5)),
+        );
+        assert_eq!(ev.latency_from(time), Some(Duration::ZERO));
+        assert_eq!(ev.latency_from(time - Duration::from_millis(5)), None);
+        assert!(ev.elapsed().is_some());
+
+        let future = InputEvent::zeroed().with_time(SystemTime::now() + Duration::from_secs(60));
+        assert_eq!(future.elapsed(), None);
+    }
+
     #[test]
     fn timestamps() {
         const EV: InputEvent = InputEvent::zeroed();
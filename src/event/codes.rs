@@ -42,10 +42,27 @@ impl InputProp {
 }
 bitvalue!(InputProp);
 
+impl InputProp {
+    pub(crate) fn name(self) -> Option<VariantName> {
+        Some(VariantName::new("INPUT_PROP_", self.variant_name()?))
+    }
+}
+
+impl FromStr for InputProp {
+    type Err = ParseVariantError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.strip_prefix("INPUT_PROP_") {
+            Some(v) => Self::from_variant_name(v).ok_or(ParseVariantError { _p: () }),
+            None => Err(ParseVariantError { _p: () }),
+        }
+    }
+}
+
 impl fmt::Debug for InputProp {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self.variant_name() {
-            Some(name) => write!(f, "INPUT_PROP_{name}"),
+        match self.name() {
+            Some(name) => name.fmt(f),
             None => write!(f, "InputProp({:#x})", self.0),
         }
     }
@@ -89,13 +106,57 @@ ffi_enum! {
 }
 impl EventType {
     const MAX: Self = Self(0x1f);
+
+    /// Returns whether events of this type change device state that can be queried via `ioctl`s.
+    ///
+    /// This is the case for [`EventType::KEY`], [`EventType::LED`], [`EventType::SND`],
+    /// [`EventType::SW`], and [`EventType::ABS`].
+    /// Dropping or filtering these events will cause the application's view of the device state
+    /// to go out of sync.
+    /// [`EventReader`][crate::reader::EventReader] tracks the state of these event types.
+    pub const fn is_state_bearing(self) -> bool {
+        matches!(
+            self,
+            Self::KEY | Self::LED | Self::SND | Self::SW | Self::ABS
+        )
+    }
+
+    /// Returns whether events of this type carry no persistent device state.
+    ///
+    /// This is the case for [`EventType::SYN`], [`EventType::REL`], and [`EventType::MSC`].
+    /// These events only describe something that happened at a point in time, so they can be
+    /// filtered without affecting the device state.
+    ///
+    /// Note that event types that are neither state-bearing nor stateless exist (for example,
+    /// [`EventType::REP`] and [`EventType::FF`], which are used to *control* the device), so this
+    /// is not the inverse of [`EventType::is_state_bearing`].
+    pub const fn is_stateless(self) -> bool {
+        matches!(self, Self::SYN | Self::REL | Self::MSC)
+    }
 }
 bitvalue!(EventType);
 
+impl EventType {
+    pub(crate) fn name(self) -> Option<VariantName> {
+        Some(VariantName::new("EV_", self.variant_name()?))
+    }
+}
+
+impl FromStr for EventType {
+    type Err = ParseVariantError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.strip_prefix("EV_") {
+            Some(v) => Self::from_variant_name(v).ok_or(ParseVariantError { _p: () }),
+            None => Err(ParseVariantError { _p: () }),
+        }
+    }
+}
+
 impl fmt::Debug for EventType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self.variant_name() {
-            Some(name) => write!(f, "EV_{name}"),
+        match self.name() {
+            Some(name) => name.fmt(f),
             None => write!(f, "EventType({:#x})", self.0),
         }
     }
@@ -167,6 +228,7 @@ ffi_enum! {
     /// Note that there are aliased key codes: several associated constants of this type have the same
     /// value and will compare equal. When formatting such a [`Key`], the name of one of the
     /// constants will be used, but which one it is is unspecified.
+    /// [`Key::aliases`] can be used to pick a specific name instead.
     pub enum Key: u16 {
         KEY_RESERVED     = 0,
         KEY_ESC          = 1,
@@ -878,6 +940,27 @@ impl Key {
         // This one has no shared prefix, since both `KEY_` and `BTN_` constants exist.
         Some(VariantName::new("", self.variant_name()?))
     }
+
+    /// Returns an iterator over the names of all constants that have the same value as `self`.
+    ///
+    /// Some key codes have several names (eg. `BTN_SOUTH`, `BTN_GAMEPAD`, and `BTN_A`).
+    /// While the `Debug` and `Display` impls pick one of them, applications that care about
+    /// which name is shown (for example, to prefer `BTN_*` names in a gamepad UI) can use this
+    /// method to choose one themselves.
+    ///
+    /// The iterator is empty if there is no constant for `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use evdevil::event::Key;
+    ///
+    /// let names = Key::BTN_A.aliases().collect::<Vec<_>>();
+    /// assert_eq!(names, ["BTN_SOUTH", "BTN_GAMEPAD", "BTN_A"]);
+    /// ```
+    pub fn aliases(self) -> impl Iterator<Item = &'static str> {
+        self.variant_names()
+    }
 }
 
 impl FromStr for Key {
@@ -1332,6 +1415,45 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn key_aliases() {
+        assert_eq!(
+            Key::KEY_HANGUEL.aliases().collect::<Vec<_>>(),
+            ["KEY_HANGEUL", "KEY_HANGUEL"],
+        );
+        assert_eq!(Key::KEY_ESC.aliases().collect::<Vec<_>>(), ["KEY_ESC"]);
+        assert_eq!(Key::from_raw(Key::MAX.raw()).aliases().count(), 0);
+    }
+
+    #[test]
+    fn all_named() {
+        let keys: Vec<_> = Key::all_named().collect();
+        assert_eq!(keys[0], Key::KEY_RESERVED);
+        assert!(keys.contains(&Key::KEY_WWAN));
+        assert_eq!(keys.iter().filter(|&&k| k == Key::KEY_WWAN).count(), 1);
+        assert_eq!(keys.iter().filter(|&&k| k == Key::BTN_0).count(), 1);
+        assert!(keys.iter().all(|k| k.variant_name().is_some()));
+
+        assert_eq!(
+            EventType::all_named().count(),
+            EventType::VARIANTS.len(),
+            "`EventType` has no aliases",
+        );
+        assert!(Abs::all_named().any(|abs| abs == Abs::MT_TOOL_TYPE));
+    }
+
+    #[test]
+    fn state_bearing() {
+        assert!(EventType::KEY.is_state_bearing());
+        assert!(EventType::ABS.is_state_bearing());
+        assert!(!EventType::KEY.is_stateless());
+        assert!(EventType::REL.is_stateless());
+        assert!(EventType::SYN.is_stateless());
+        assert!(!EventType::REL.is_state_bearing());
+        assert!(!EventType::FF.is_state_bearing());
+        assert!(!EventType::FF.is_stateless());
+    }
+
     #[test]
     fn str_repr() {
         assert_eq!(format!("{:?}", InputProp::POINTER), "INPUT_PROP_POINTER");
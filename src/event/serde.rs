@@ -4,14 +4,17 @@ use std::{any::type_name, fmt, marker::PhantomData, str::FromStr};
 
 use serde_core::{Deserialize, Deserializer, Serialize, Serializer, de};
 
-use crate::event::{Abs, Key, Led, Misc, Rel, Sound, Switch};
+use crate::{
+    InputProp,
+    event::{Abs, EventType, Key, Led, Misc, Rel, Sound, Switch},
+};
 
-struct NamedOrRawVisitor<T: FromStr, F: Fn(u16) -> T> {
+struct NamedOrRawVisitor<T: FromStr, F: Fn(u16) -> Option<T>> {
     from_raw: F,
     _p: PhantomData<T>,
 }
 
-impl<T: FromStr, F: Fn(u16) -> T> NamedOrRawVisitor<T, F> {
+impl<T: FromStr, F: Fn(u16) -> Option<T>> NamedOrRawVisitor<T, F> {
     fn new(from_raw: F) -> Self {
         Self {
             from_raw,
@@ -20,7 +23,7 @@ impl<T: FromStr, F: Fn(u16) -> T> NamedOrRawVisitor<T, F> {
     }
 }
 
-impl<'de, T: FromStr, F: Fn(u16) -> T> de::Visitor<'de> for NamedOrRawVisitor<T, F> {
+impl<'de, T: FromStr, F: Fn(u16) -> Option<T>> de::Visitor<'de> for NamedOrRawVisitor<T, F> {
     type Value = T;
 
     fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
@@ -43,7 +46,8 @@ impl<'de, T: FromStr, F: Fn(u16) -> T> de::Visitor<'de> for NamedOrRawVisitor<T,
     where
         E: de::Error,
     {
-        Ok((self.from_raw)(v))
+        (self.from_raw)(v)
+            .ok_or_else(|| E::invalid_value(de::Unexpected::Unsigned(v.into()), &"valid raw code"))
     }
 
     fn visit_u32<E>(self, v: u32) -> Result<Self::Value, E>
@@ -80,9 +84,9 @@ impl<'de, T: FromStr, F: Fn(u16) -> T> de::Visitor<'de> for NamedOrRawVisitor<T,
 }
 
 macro_rules! serde_impls {
-    ( $($t:ident),* ) => {
+    ( $($t:ident: $raw:ty),* ) => {
         $(
-            /// Deserialization from a raw 16-bit code or a named constant.
+            /// Deserialization from a raw code or a named constant.
             impl<'a> Deserialize<'a> for $t {
                 fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
                 where
@@ -93,10 +97,12 @@ macro_rules! serde_impls {
                         // (`KEY_F1`, `ABS_X`, ...) or as raw `u16` values.
                         // We assume that all human-readable formats are also self-describing and
                         // thus support `deserialize_any`.
-                        deserializer.deserialize_any(NamedOrRawVisitor::new(<$t>::from_raw))
+                        deserializer.deserialize_any(NamedOrRawVisitor::new(|raw| {
+                            <$raw>::try_from(raw).ok().map(<$t>::from_raw)
+                        }))
                     } else {
-                        // Binary formats always use the raw u16 code.
-                        let raw = u16::deserialize(deserializer)?;
+                        // Binary formats always use the raw code.
+                        let raw = <$raw>::deserialize(deserializer)?;
                         Ok(<$t>::from_raw(raw))
                     }
                 }
@@ -109,14 +115,14 @@ macro_rules! serde_impls {
                 {
                     if serializer.is_human_readable() {
                         // For human-readable formats, we prefer the textual name if there is one.
-                        // If not, we use the raw u16 code.
+                        // If not, we use the raw code.
                         // Like above, we assume human readable formats are also self-describing.
                         match self.name() {
                             Some(name) => serializer.collect_str(&name),
                             None => self.raw().serialize(serializer),
                         }
                     } else {
-                        // Binary formats always use the raw u16 code.
+                        // Binary formats always use the raw code.
                         self.raw().serialize(serializer)
                     }
                 }
@@ -125,7 +131,17 @@ macro_rules! serde_impls {
     };
 }
 
-serde_impls!(Abs, Key, Rel, Misc, Led, Switch, Sound);
+serde_impls!(
+    Abs: u16,
+    Key: u16,
+    Rel: u16,
+    Misc: u16,
+    Led: u16,
+    Switch: u16,
+    Sound: u16,
+    EventType: u16,
+    InputProp: u8
+);
 
 #[cfg(test)]
 mod tests {
@@ -159,6 +175,39 @@ mod tests {
         assert!(iter.next().is_none());
     }
 
+    #[test]
+    fn input_prop_event_type() {
+        let mut out = Vec::new();
+        let mut w = WriterBuilder::new().from_writer(&mut out);
+        w.serialize(InputProp::DIRECT).unwrap();
+        w.serialize(EventType::ABS).unwrap();
+        w.flush().unwrap();
+        drop(w);
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "INPUT_PROP_DIRECT\nEV_ABS\n"
+        );
+
+        let mut r = ReaderBuilder::new()
+            .has_headers(false)
+            .from_reader("INPUT_PROP_DIRECT\n300\n".as_bytes());
+        let mut iter = r.deserialize::<InputProp>();
+        assert_eq!(iter.next().unwrap().unwrap(), InputProp::DIRECT);
+        // Out of range for the `u8` code.
+        assert!(iter.next().unwrap().is_err());
+
+        let b = postcard::to_allocvec(&EventType::ABS).unwrap();
+        assert_eq!(
+            postcard::from_bytes::<EventType>(&b).unwrap(),
+            EventType::ABS
+        );
+        let b = postcard::to_allocvec(&InputProp::from_raw(0x1f)).unwrap();
+        assert_eq!(
+            postcard::from_bytes::<InputProp>(&b).unwrap(),
+            InputProp::from_raw(0x1f)
+        );
+    }
+
     #[test]
     fn postcard() {
         let b = postcard::to_allocvec(&Key::KEY_F12).unwrap();
@@ -35,18 +35,34 @@
 //! The uploaded effect can then be triggered via [`Evdev::control_ff`], or erased by calling
 //! [`Evdev::erase_ff_effect`].
 //!
+//! # Serialization
+//!
+//! If the `serde` feature is enabled, implementations of [`Serialize`] and [`Deserialize`] will be
+//! provided for [`EffectKind`] and the effect parameter types it is made of, which allows storing
+//! libraries of effects in data files.
+//!
+//! Since the waveform data of [`Periodic`] effects with a custom waveform is borrowed, such effects
+//! can only be serialized, not deserialized.
+//! To load them, deserialize an [`OwnedPeriodic`] instead, which uses the same representation and
+//! accepts any [`Periodic`] effect.
+//!
 //! [`Evdev::supported_ff_features`]: crate::Evdev::supported_ff_features
 //! [`Evdev::supported_ff_effects`]: crate::Evdev::supported_ff_effects
 //! [`Evdev::upload_ff_effect`]: crate::Evdev::upload_ff_effect
 //! [`Evdev::control_ff`]: crate::Evdev::control_ff
 //! [`Evdev::erase_ff_effect`]: crate::Evdev::erase_ff_effect
+//! [`Serialize`]: ::serde_core::Serialize
+//! [`Deserialize`]: ::serde_core::Deserialize
+
+#[cfg(any(test, feature = "serde"))]
+mod serde;
 
 use std::{
-    fmt,
+    fmt, io,
     marker::PhantomData,
     mem,
     ops::{Deref, DerefMut},
-    slice,
+    ptr, slice,
 };
 
 use crate::{
@@ -317,6 +333,14 @@ impl fmt::Debug for Replay {
 #[repr(transparent)]
 pub struct Envelope(ff_envelope);
 
+/// Multiplies `value` by `factor` and rounds the result.
+///
+/// Negative and NaN factors are treated as 0.
+/// Callers cast the result back to the integer type, which saturates.
+fn scale<T: Into<f32>>(value: T, factor: f32) -> f32 {
+    (value.into() * factor.max(0.0)).round()
+}
+
 impl Envelope {
     /// Returns a zeroed-out default [`Envelope`] structure.
     ///
@@ -332,6 +356,14 @@ impl Envelope {
         })
     }
 
+    /// Scales the attack and fade levels by `factor`.
+    fn scaled(mut self, factor: f32) -> Self {
+        // Float-to-int `as` casts saturate.
+        self.0.attack_level = scale(self.0.attack_level, factor) as u16;
+        self.0.fade_level = scale(self.0.fade_level, factor) as u16;
+        self
+    }
+
     /// Effect will be faded in for `ms` milliseconds before reaching its full intensity.
     #[inline]
     pub fn with_attack_length(mut self, ms: u16) -> Self {
@@ -428,6 +460,24 @@ impl Direction {
     pub const fn raw(self) -> u16 {
         self.0
     }
+
+    /// Creates a [`Direction`] pointing along the 2D vector `(x, y)`.
+    ///
+    /// The vector uses the same coordinate system as [`Abs::X`] and [`Abs::Y`]: positive `x`
+    /// points right, and positive `y` points *down*.
+    /// So `(0, 1)` results in [`Direction::DOWN`] (0°), `(-1, 0)` in [`Direction::LEFT`], `(0, -1)`
+    /// in [`Direction::UP`], and `(1, 0)` in [`Direction::RIGHT`].
+    ///
+    /// Only the direction of the vector matters, not its length.
+    /// A zero-length vector results in [`Direction::DOWN`].
+    ///
+    /// [`Abs::X`]: crate::event::Abs::X
+    /// [`Abs::Y`]: crate::event::Abs::Y
+    pub fn from_vector(x: f32, y: f32) -> Self {
+        let turns = f32::atan2(-x, y) / std::f32::consts::TAU;
+        // `turns` is in `-0.5..=0.5`. Casting through `i32` wraps negative angles around.
+        Self((turns * 65536.0).round() as i32 as u16)
+    }
 }
 
 /// A force-feedback effect description.
@@ -460,6 +510,9 @@ impl Effect<'_> {
     ///
     /// The ID can be set to an existing [`EffectId`] in order to reconfigure an already uploaded
     /// effect.
+    /// [`Evdev::update_ff_effect`] does this for you.
+    ///
+    /// [`Evdev::update_ff_effect`]: crate::Evdev::update_ff_effect
     #[inline]
     pub fn with_id(mut self, id: EffectId) -> Self {
         self.raw.id = id.0;
@@ -472,12 +525,32 @@ impl Effect<'_> {
         self
     }
 
+    /// Sets the direction of this effect to point along the 2D vector `(x, y)`.
+    ///
+    /// This is useful when the force is computed by a physics simulation.
+    /// See [`Direction::from_vector`] for the coordinate convention.
+    #[inline]
+    pub fn with_direction_vector(self, x: f32, y: f32) -> Self {
+        self.with_direction(Direction::from_vector(x, y))
+    }
+
+    /// Sets the [`Trigger`] that starts this effect.
+    ///
+    /// By default, effects use [`Trigger::none`], so they only play when started explicitly via
+    /// [`Evdev::control_ff`].
+    ///
+    /// [`Evdev::control_ff`]: crate::Evdev::control_ff
     #[inline]
     pub fn with_trigger(mut self, trigger: Trigger) -> Self {
         self.raw.trigger = trigger.0;
         self
     }
 
+    /// Sets the [`Replay`] parameters (length and start delay) of this effect.
+    ///
+    /// By default, both the length and the delay are 0.
+    /// A length of 0 is interpreted by most drivers as "play until stopped", but this is not
+    /// guaranteed.
     #[inline]
     pub fn with_replay(mut self, replay: Replay) -> Self {
         self.raw.replay = replay.0;
@@ -545,6 +618,18 @@ impl Effect<'_> {
     }
 }
 
+impl PartialEq for Effect<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        // `ff_effect` contains a union, so this has to compare only the active variant.
+        self.effect_type() == other.effect_type()
+            && self.id() == other.id()
+            && self.direction() == other.direction()
+            && self.trigger() == other.trigger()
+            && self.replay() == other.replay()
+            && self.kind() == other.kind()
+    }
+}
+
 impl fmt::Debug for Effect<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Effect")
@@ -714,6 +799,49 @@ pub enum EffectKind<'a> {
     __Unknown(internal::UnknownEffect),
 }
 
+impl EffectKind<'_> {
+    /// Returns the number of axes this effect has parameters for.
+    ///
+    /// This is 2 for the [`Condition`]-based effects ([`Spring`], [`Friction`], [`Damper`], and
+    /// [`Inertia`]), which carry one [`Condition`] per axis, and 1 for all other effects.
+    pub fn axis_count(&self) -> usize {
+        match self {
+            Self::Spring(_) | Self::Friction(_) | Self::Damper(_) | Self::Inertia(_) => 2,
+            _ => 1,
+        }
+    }
+
+    /// Returns an iterator over the per-axis [`Condition`]s of this effect.
+    ///
+    /// For [`Spring`], [`Friction`], [`Damper`], and [`Inertia`] effects, this yields the
+    /// [`Condition`] of each axis, in order.
+    /// For all other effects, the iterator is empty.
+    pub fn conditions(&self) -> impl Iterator<Item = &Condition> {
+        let conditions = match self {
+            Self::Spring([a, b]) => Some([&**a, &**b]),
+            Self::Friction([a, b]) => Some([&**a, &**b]),
+            Self::Damper([a, b]) => Some([&**a, &**b]),
+            Self::Inertia([a, b]) => Some([&**a, &**b]),
+            _ => None,
+        };
+        conditions.into_iter().flatten()
+    }
+
+    /// Returns an iterator over mutable references to the per-axis [`Condition`]s of this effect.
+    ///
+    /// See [`EffectKind::conditions`].
+    pub fn conditions_mut(&mut self) -> impl Iterator<Item = &mut Condition> {
+        let conditions = match self {
+            Self::Spring([a, b]) => Some([&mut **a, &mut **b]),
+            Self::Friction([a, b]) => Some([&mut **a, &mut **b]),
+            Self::Damper([a, b]) => Some([&mut **a, &mut **b]),
+            Self::Inertia([a, b]) => Some([&mut **a, &mut **b]),
+            _ => None,
+        };
+        conditions.into_iter().flatten()
+    }
+}
+
 impl PartialEq for EffectKind<'_> {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
@@ -784,6 +912,18 @@ impl Rumble {
     pub fn weak_magnitude(&self) -> u16 {
         self.0.weak_magnitude
     }
+
+    /// Returns a copy of this effect with both magnitudes multiplied by `factor`.
+    ///
+    /// This can be used to implement a device-independent intensity setting, since not all
+    /// devices support [`Feature::GAIN`].
+    /// The magnitudes saturate at [`u16::MAX`], and negative factors are treated as 0.
+    pub fn scaled(&self, factor: f32) -> Self {
+        Self::new(
+            scale(self.strong_magnitude(), factor) as u16,
+            scale(self.weak_magnitude(), factor) as u16,
+        )
+    }
 }
 impl fmt::Debug for Rumble {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -808,6 +948,12 @@ pub struct Periodic<'a> {
 }
 
 impl<'a> Periodic<'a> {
+    /// The maximum number of samples [`Periodic::try_custom`] accepts for a custom waveform.
+    ///
+    /// This limit is imposed by this crate, not by the kernel or any driver, and is meant to
+    /// reject obviously invalid waveform data early. Devices may reject much shorter waveforms.
+    pub const MAX_CUSTOM_LEN: usize = u16::MAX as usize;
+
     /// Creates a simple [`Periodic`] effect with a fixed [`Waveform`].
     #[inline]
     pub fn simple(waveform: Waveform, period: u16, magnitude: i16) -> Periodic<'a> {
@@ -841,6 +987,49 @@ impl<'a> Periodic<'a> {
         p
     }
 
+    /// Creates a custom waveform effect, checking the length of `data`.
+    ///
+    /// This is like [`Periodic::custom`], but returns an error instead of panicking, which is
+    /// useful when the waveform data comes from an untrusted source like a file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of type [`io::ErrorKind::InvalidInput`] if `data` is empty or contains
+    /// more than [`Periodic::MAX_CUSTOM_LEN`] samples.
+    pub fn try_custom(data: &'a [i16]) -> io::Result<Periodic<'a>> {
+        if data.is_empty() || data.len() > Self::MAX_CUSTOM_LEN {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "custom waveform has {} samples, expected 1 to {}",
+                    data.len(),
+                    Self::MAX_CUSTOM_LEN,
+                ),
+            ));
+        }
+        Ok(Self::custom(data))
+    }
+
+    /// Creates a custom waveform effect that owns its waveform data.
+    ///
+    /// This is like [`Periodic::custom`], but the returned [`OwnedPeriodic`] does not borrow from
+    /// anything, so it can be stored and passed around freely, which is useful when the waveform
+    /// is generated at runtime.
+    ///
+    /// # Panics
+    ///
+    /// Panics when `data` is longer than [`u32::MAX`] elements.
+    pub fn custom_owned(data: Vec<i16>) -> OwnedPeriodic {
+        assert!(
+            u32::try_from(data.len()).is_ok(),
+            "custom waveform data too long"
+        );
+        OwnedPeriodic {
+            template: Periodic::simple(Waveform::CUSTOM, 0, 0),
+            data,
+        }
+    }
+
     #[inline]
     pub fn with_envelope(mut self, env: Envelope) -> Self {
         self.raw.envelope = env.0;
@@ -859,6 +1048,18 @@ impl<'a> Periodic<'a> {
         self
     }
 
+    /// Returns a copy of this effect with its magnitude, offset, and [`Envelope`] levels
+    /// multiplied by `factor`.
+    ///
+    /// See [`Rumble::scaled`] for details.
+    /// Custom waveform data is not modified.
+    pub fn scaled(&self, factor: f32) -> Self {
+        let mut this = *self;
+        this.raw.magnitude = scale(self.magnitude(), factor) as i16;
+        this.raw.offset = scale(self.offset(), factor) as i16;
+        this.with_envelope(self.envelope().scaled(factor))
+    }
+
     /// Returns the type of [`Waveform`] described by this [`Periodic`] effect.
     #[inline]
     pub fn waveform(&self) -> Waveform {
@@ -940,6 +1141,107 @@ impl fmt::Debug for Periodic<'_> {
     }
 }
 
+/// A [`Periodic`] effect with a custom waveform that owns its waveform data.
+///
+/// Created by [`Periodic::custom_owned`].
+/// [`OwnedPeriodic`] can also be converted from any [`Periodic`] effect, in which case it will
+/// have no waveform data unless the effect uses [`Waveform::CUSTOM`].
+///
+/// The waveform data only has to live until the effect has been uploaded to the device, since the
+/// kernel makes a copy of it.
+/// [`&OwnedPeriodic`][OwnedPeriodic] can be converted to an [`Effect`] (borrowing the waveform data)
+/// and passed to [`Evdev::upload_ff_effect`] directly.
+///
+/// [`Evdev::upload_ff_effect`]: crate::Evdev::upload_ff_effect
+#[derive(Clone, PartialEq, Eq)]
+pub struct OwnedPeriodic {
+    /// Stores all parameters except the waveform data. `custom_data` is always null.
+    /// `data` is empty unless this is a [`Waveform::CUSTOM`] effect.
+    template: Periodic<'static>,
+    data: Vec<i16>,
+}
+
+impl OwnedPeriodic {
+    #[inline]
+    pub fn with_envelope(mut self, env: Envelope) -> Self {
+        self.template = self.template.with_envelope(env);
+        self
+    }
+
+    #[inline]
+    pub fn with_offset(mut self, offset: i16) -> Self {
+        self.template = self.template.with_offset(offset);
+        self
+    }
+
+    #[inline]
+    pub fn with_phase(mut self, phase: u16) -> Self {
+        self.template = self.template.with_phase(phase);
+        self
+    }
+
+    /// Returns the custom waveform data.
+    #[inline]
+    pub fn data(&self) -> &[i16] {
+        &self.data
+    }
+
+    /// Consumes `self` and returns the custom waveform data.
+    #[inline]
+    pub fn into_data(self) -> Vec<i16> {
+        self.data
+    }
+
+    /// Returns a [`Periodic`] effect that borrows the waveform data from `self`.
+    pub fn as_periodic(&self) -> Periodic<'_> {
+        let mut p = self.template;
+        if p.waveform() != Waveform::CUSTOM {
+            return p;
+        }
+        p.raw.custom_len = self.data.len() as u32;
+        p.raw.custom_data = self.data.as_ptr().cast_mut();
+        p
+    }
+}
+
+impl fmt::Debug for OwnedPeriodic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("OwnedPeriodic")
+            .field(&self.as_periodic())
+            .finish()
+    }
+}
+
+impl From<Periodic<'_>> for OwnedPeriodic {
+    fn from(value: Periodic<'_>) -> Self {
+        let mut template = Periodic {
+            raw: value.raw,
+            _p: PhantomData,
+        };
+        template.raw.custom_len = 0;
+        template.raw.custom_data = ptr::null_mut();
+        let data = match value.custom_data() {
+            Some(data) if value.waveform() == Waveform::CUSTOM => data.to_vec(),
+            _ => Vec::new(),
+        };
+        Self { template, data }
+    }
+}
+
+impl<'a> From<&'a OwnedPeriodic> for Periodic<'a> {
+    #[inline]
+    fn from(value: &'a OwnedPeriodic) -> Self {
+        value.as_periodic()
+    }
+}
+
+impl<'a> From<&'a OwnedPeriodic> for Effect<'a> {
+    #[inline]
+    fn from(value: &'a OwnedPeriodic) -> Self {
+        Self::from(value.as_periodic())
+    }
+}
+
 /// An effect that applies a constant force.
 #[derive(Clone, Copy, PartialEq, Eq)]
 #[repr(transparent)]
@@ -970,6 +1272,14 @@ impl Constant {
         self.0.level
     }
 
+    /// Returns a copy of this effect with its level and [`Envelope`] levels multiplied by
+    /// `factor`.
+    ///
+    /// See [`Rumble::scaled`] for details.
+    pub fn scaled(&self, factor: f32) -> Self {
+        Self::new(scale(self.level(), factor) as i16).with_envelope(self.envelope().scaled(factor))
+    }
+
     #[inline]
     pub fn envelope(&self) -> Envelope {
         Envelope(self.0.envelope)
@@ -1020,6 +1330,18 @@ impl Ramp {
         self.0.end_level
     }
 
+    /// Returns a copy of this effect with its start, end, and [`Envelope`] levels multiplied by
+    /// `factor`.
+    ///
+    /// See [`Rumble::scaled`] for details.
+    pub fn scaled(&self, factor: f32) -> Self {
+        Self::new(
+            scale(self.start_level(), factor) as i16,
+            scale(self.end_level(), factor) as i16,
+        )
+        .with_envelope(self.envelope().scaled(factor))
+    }
+
     #[inline]
     pub fn envelope(&self) -> Envelope {
         Envelope(self.0.envelope)
@@ -1304,6 +1626,60 @@ mod tests {
         assert::<EffectId>();
     }
 
+    #[test]
+    fn direction_from_vector() {
+        assert_eq!(Direction::from_vector(0.0, 1.0), Direction::DOWN);
+        assert_eq!(Direction::from_vector(-1.0, 0.0), Direction::LEFT);
+        assert_eq!(Direction::from_vector(0.0, -1.0), Direction::UP);
+        assert_eq!(Direction::from_vector(1.0, 0.0), Direction::RIGHT);
+        assert_eq!(Direction::from_vector(5.0, 0.0), Direction::RIGHT);
+        assert_eq!(Direction::from_vector(0.0, 0.0), Direction::DOWN);
+        assert_eq!(
+            Direction::from_vector(1.0, 1.0),
+            Direction::from_raw(0xE000)
+        );
+        assert_eq!(
+            Effect::from(Rumble::new(1, 1))
+                .with_direction_vector(-1.0, 0.0)
+                .direction(),
+            Direction::LEFT,
+        );
+    }
+
+    #[test]
+    fn scaled() {
+        assert_eq!(
+            Rumble::new(1000, 0x8000).scaled(0.5),
+            Rumble::new(500, 0x4000)
+        );
+        assert_eq!(
+            Rumble::new(1000, 0x8000).scaled(3.0),
+            Rumble::new(3000, u16::MAX)
+        );
+        assert_eq!(Rumble::new(1000, 1000).scaled(-1.0), Rumble::new(0, 0));
+        assert_eq!(Rumble::new(1000, 1000).scaled(f32::NAN), Rumble::new(0, 0));
+
+        let env = Envelope::new()
+            .with_attack_level(100)
+            .with_attack_length(50);
+        let constant = Constant::new(-1000).with_envelope(env).scaled(0.5);
+        assert_eq!(constant.level(), -500);
+        assert_eq!(constant.envelope().attack_level(), 50);
+        assert_eq!(constant.envelope().attack_length(), 50);
+
+        assert_eq!(Constant::new(i16::MIN).scaled(2.0).level(), i16::MIN);
+        assert_eq!(Ramp::new(100, -100).scaled(0.5), Ramp::new(50, -50));
+
+        let periodic = Periodic::simple(Waveform::SINE, 100, 1000)
+            .with_offset(200)
+            .with_phase(7)
+            .scaled(0.5);
+        assert_eq!(periodic.magnitude(), 500);
+        assert_eq!(periodic.offset(), 100);
+        assert_eq!(periodic.period(), 100);
+        assert_eq!(periodic.phase(), 7);
+    }
+
     #[test]
     fn custom_eq() {
         // These `Periodic` effects point to different waveforms, but those waveforms are equal.
@@ -1314,6 +1690,65 @@ mod tests {
         assert_eq!(a, b);
     }
 
+    #[test]
+    fn custom_owned() {
+        let owned = Periodic::custom_owned(vec![0, 1, 2, 1, 0]).with_phase(3);
+        let borrowed = Periodic::custom(&[0, 1, 2, 1, 0]).with_phase(3);
+        assert_eq!(owned.as_periodic(), borrowed);
+        assert_eq!(Effect::from(&owned).kind(), Effect::from(borrowed).kind());
+        assert_eq!(owned.into_data(), [0, 1, 2, 1, 0]);
+    }
+
+    #[test]
+    fn effect_eq() {
+        let a = Effect::from(Rumble::new(123, 456));
+        assert_eq!(a, Effect::from(Rumble::new(123, 456)));
+        assert_ne!(a, Effect::from(Rumble::new(123, 457)));
+        assert_ne!(a, a.with_id(EffectId(0)));
+        assert_ne!(a, a.with_replay(Replay::new(100, 0)));
+        assert_ne!(a, Effect::from(Constant::new(123)));
+
+        static BUF: &[i16] = &[0, 1, 0, 1];
+        let a = Effect::from(Periodic::custom(&BUF[0..2]));
+        let b = Effect::from(Periodic::custom(&BUF[2..4]));
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn try_custom() {
+        static DATA: [i16; Periodic::MAX_CUSTOM_LEN + 1] = [0; Periodic::MAX_CUSTOM_LEN + 1];
+
+        let p = Periodic::try_custom(&DATA[..Periodic::MAX_CUSTOM_LEN]).unwrap();
+        assert_eq!(p.custom_data().unwrap().len(), Periodic::MAX_CUSTOM_LEN);
+
+        let err = Periodic::try_custom(&DATA).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        let err = Periodic::try_custom(&[]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn effect_kind_conditions() {
+        let mut kind = EffectKind::Rumble(Rumble::new(1, 2));
+        assert_eq!(kind.axis_count(), 1);
+        assert_eq!(kind.conditions().count(), 0);
+        assert_eq!(kind.conditions_mut().count(), 0);
+
+        let mut kind = EffectKind::Damper([
+            Damper::new(Condition::new().with_center(1)),
+            Damper::new(Condition::new().with_center(2)),
+        ]);
+        assert_eq!(kind.axis_count(), 2);
+        assert_eq!(
+            kind.conditions().map(|c| c.center()).collect::<Vec<_>>(),
+            [1, 2]
+        );
+        for c in kind.conditions_mut() {
+            *c = c.with_deadband(50);
+        }
+        assert!(kind.conditions().all(|c| c.deadband() == 50));
+    }
+
     #[test]
     fn effect_kind_unk() {
         let mut effect = Effect::from(Rumble::new(123, 456));
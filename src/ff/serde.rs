@@ -0,0 +1,546 @@
+#![cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+
+use std::fmt;
+
+use serde_core::{
+    Deserialize, Deserializer, Serialize, Serializer,
+    de::{self, EnumAccess, MapAccess, SeqAccess, VariantAccess},
+    ser::{self, SerializeStruct},
+};
+
+use crate::{
+    event::Key,
+    ff::{
+        Condition, Constant, Damper, EffectKind, Envelope, Friction, Inertia, OwnedPeriodic,
+        Periodic, Ramp, Replay, Rumble, Spring, Trigger, Waveform,
+    },
+};
+
+/// Implements `Serialize` and/or `Deserialize` for an effect parameter type.
+///
+/// The type is represented as a struct whose fields are read via getters of the same name.
+/// Fields with a default value may be omitted when deserializing from a self-describing format.
+macro_rules! struct_impls {
+    (
+        $t:ty, $name:literal {
+            $( $field:ident: $fty:ty $(= $default:expr)? ),* $(,)?
+        } => $build:expr
+    ) => {
+        struct_impls!(@ser $t, $name { $($field),* });
+        struct_impls!(@de $t, $name { $($field: $fty $(= $default)?),* } => $build);
+    };
+
+    (@ser $t:ty, $name:literal { $($field:ident),* $(,)? }) => {
+        impl Serialize for $t {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                const LEN: usize = [$(stringify!($field)),*].len();
+                let mut s = serializer.serialize_struct($name, LEN)?;
+                $( s.serialize_field(stringify!($field), &self.$field())?; )*
+                s.end()
+            }
+        }
+    };
+
+    (@de $t:ty, $name:literal {
+        $( $field:ident: $fty:ty $(= $default:expr)? ),* $(,)?
+    } => $build:expr) => {
+        impl<'de> Deserialize<'de> for $t {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                const FIELDS: &[&str] = &[$(stringify!($field)),*];
+
+                struct Visitor;
+
+                impl<'de> de::Visitor<'de> for Visitor {
+                    type Value = $t;
+
+                    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                        formatter.write_str(concat!("struct ", $name))
+                    }
+
+                    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+                    where
+                        A: SeqAccess<'de>,
+                    {
+                        let mut index = 0;
+                        $(
+                            let $field: $fty = seq
+                                .next_element()?
+                                .ok_or_else(|| de::Error::invalid_length(index, &self))?;
+                            index += 1;
+                        )*
+                        let _ = index;
+                        let result: Result<$t, String> = $build;
+                        result.map_err(de::Error::custom)
+                    }
+
+                    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+                    where
+                        A: MapAccess<'de>,
+                    {
+                        $( let mut $field: Option<$fty> = None; )*
+                        while let Some(key) = map.next_key::<String>()? {
+                            match &*key {
+                                $(
+                                    stringify!($field) => {
+                                        if $field.is_some() {
+                                            return Err(de::Error::duplicate_field(
+                                                stringify!($field),
+                                            ));
+                                        }
+                                        $field = Some(map.next_value()?);
+                                    }
+                                )*
+                                _ => return Err(de::Error::unknown_field(&key, FIELDS)),
+                            }
+                        }
+                        $(
+                            let $field = match $field {
+                                Some(value) => value,
+                                None => struct_impls!(@default $field $(, $default)?),
+                            };
+                        )*
+                        let result: Result<$t, String> = $build;
+                        result.map_err(de::Error::custom)
+                    }
+                }
+
+                deserializer.deserialize_struct($name, FIELDS, Visitor)
+            }
+        }
+    };
+
+    (@default $field:ident) => {
+        return Err(de::Error::missing_field(stringify!($field)))
+    };
+    (@default $field:ident, $default:expr) => {
+        $default
+    };
+}
+
+struct_impls!(Trigger, "Trigger" {
+    button: Key,
+    interval: u16,
+} => Ok(Trigger::new(button, interval)));
+
+struct_impls!(Replay, "Replay" {
+    length: u16,
+    delay: u16,
+} => Ok(Replay::new(length, delay)));
+
+struct_impls!(Envelope, "Envelope" {
+    attack_length: u16,
+    attack_level: u16,
+    fade_length: u16,
+    fade_level: u16,
+} => Ok(Envelope::new()
+    .with_attack_length(attack_length)
+    .with_attack_level(attack_level)
+    .with_fade_length(fade_length)
+    .with_fade_level(fade_level)));
+
+struct_impls!(Rumble, "Rumble" {
+    strong_magnitude: u16,
+    weak_magnitude: u16,
+} => Ok(Rumble::new(strong_magnitude, weak_magnitude)));
+
+struct_impls!(Constant, "Constant" {
+    level: i16,
+    envelope: Envelope = Envelope::new(),
+} => Ok(Constant::new(level).with_envelope(envelope)));
+
+struct_impls!(Ramp, "Ramp" {
+    start_level: i16,
+    end_level: i16,
+    envelope: Envelope = Envelope::new(),
+} => Ok(Ramp::new(start_level, end_level).with_envelope(envelope)));
+
+struct_impls!(Condition, "Condition" {
+    right_saturation: u16,
+    left_saturation: u16,
+    right_coeff: i16,
+    left_coeff: i16,
+    deadband: u16,
+    center: i16,
+} => Ok(Condition::new()
+    .with_right_saturation(right_saturation)
+    .with_left_saturation(left_saturation)
+    .with_right_coeff(right_coeff)
+    .with_left_coeff(left_coeff)
+    .with_deadband(deadband)
+    .with_center(center)));
+
+struct_impls!(@ser Periodic<'_>, "Periodic" {
+    waveform,
+    period,
+    magnitude,
+    offset,
+    phase,
+    envelope,
+    custom_data,
+});
+
+struct_impls!(@de OwnedPeriodic, "Periodic" {
+    waveform: Waveform,
+    period: u16,
+    magnitude: i16,
+    offset: i16,
+    phase: u16,
+    envelope: Envelope = Envelope::new(),
+    custom_data: Option<Vec<i16>> = None,
+} => {
+    let template = Periodic::simple(waveform, period, magnitude)
+        .with_offset(offset)
+        .with_phase(phase)
+        .with_envelope(envelope);
+    match (waveform == Waveform::CUSTOM, custom_data) {
+        (true, Some(data)) => match Periodic::try_custom(&data) {
+            Ok(_) => Ok(OwnedPeriodic { template, data }),
+            Err(e) => Err(e.to_string()),
+        },
+        (true, None) => Err(format!("{waveform:?} effect is missing `custom_data`")),
+        (false, None) => Ok(OwnedPeriodic {
+            template,
+            data: Vec::new(),
+        }),
+        (false, Some(_)) => Err(format!("{waveform:?} effect must not have `custom_data`")),
+    }
+});
+
+impl Serialize for OwnedPeriodic {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.as_periodic().serialize(serializer)
+    }
+}
+
+/// Deserialization of [`Periodic`] effects with a built-in [`Waveform`].
+///
+/// Effects with a custom waveform cannot borrow their waveform data from the deserializer, and
+/// have to be deserialized as an [`OwnedPeriodic`] instead.
+impl<'de> Deserialize<'de> for Periodic<'static> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let owned = OwnedPeriodic::deserialize(deserializer)?;
+        if owned.template.waveform() == Waveform::CUSTOM {
+            return Err(de::Error::custom(
+                "custom waveforms have to be deserialized as `OwnedPeriodic`",
+            ));
+        }
+        Ok(owned.template)
+    }
+}
+
+macro_rules! condition_impls {
+    ( $($t:ident),* ) => {
+        $(
+            /// Serialized like the wrapped [`Condition`].
+            impl Serialize for $t {
+                fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+                where
+                    S: Serializer,
+                {
+                    self.0.serialize(serializer)
+                }
+            }
+
+            impl<'de> Deserialize<'de> for $t {
+                fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                where
+                    D: Deserializer<'de>,
+                {
+                    Condition::deserialize(deserializer).map(Self)
+                }
+            }
+        )*
+    };
+}
+
+condition_impls!(Spring, Friction, Damper, Inertia);
+
+/// Serialization as the evdev constant name (eg. `FF_SINE`) or the raw code.
+///
+/// Like the event code types, human-readable formats use the name if there is one, while binary
+/// formats always use the raw code.
+impl Serialize for Waveform {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self.variant_name() {
+            Some(name) if serializer.is_human_readable() => {
+                serializer.collect_str(&format_args!("FF_{name}"))
+            }
+            _ => self.raw().serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Waveform {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl de::Visitor<'_> for Visitor {
+            type Value = Waveform;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("waveform name or raw code")
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                v.strip_prefix("FF_")
+                    .and_then(Waveform::from_variant_name)
+                    .ok_or_else(|| E::custom(format!("unknown waveform '{v}'")))
+            }
+
+            fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                match u16::try_from(v) {
+                    Ok(raw) => Ok(Waveform::from_raw(raw)),
+                    Err(_) => Err(E::invalid_value(
+                        de::Unexpected::Unsigned(v),
+                        &"unsigned 16-bit value",
+                    )),
+                }
+            }
+        }
+
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(Visitor)
+        } else {
+            u16::deserialize(deserializer).map(Waveform::from_raw)
+        }
+    }
+}
+
+macro_rules! effect_kind_impls {
+    ( $($variant:ident),* ) => {
+        const VARIANTS: &[&str] = &[$(stringify!($variant)),*];
+
+        #[derive(Clone, Copy)]
+        enum Variant {
+            $($variant),*
+        }
+
+        impl<'de> Deserialize<'de> for Variant {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                struct Visitor;
+
+                impl de::Visitor<'_> for Visitor {
+                    type Value = Variant;
+
+                    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                        formatter.write_str("effect kind")
+                    }
+
+                    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+                    where
+                        E: de::Error,
+                    {
+                        match v {
+                            $( stringify!($variant) => Ok(Variant::$variant), )*
+                            _ => Err(E::unknown_variant(v, VARIANTS)),
+                        }
+                    }
+
+                    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+                    where
+                        E: de::Error,
+                    {
+                        const ALL: &[Variant] = &[$(Variant::$variant),*];
+                        usize::try_from(v)
+                            .ok()
+                            .and_then(|i| ALL.get(i).copied())
+                            .ok_or_else(|| {
+                                E::invalid_value(de::Unexpected::Unsigned(v), &"variant index")
+                            })
+                    }
+                }
+
+                deserializer.deserialize_identifier(Visitor)
+            }
+        }
+
+        /// Serialization as an externally tagged enum.
+        ///
+        /// The fallback variant for unknown effect types cannot be serialized.
+        impl Serialize for EffectKind<'_> {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                match self {
+                    $(
+                        Self::$variant(v) => serializer.serialize_newtype_variant(
+                            "EffectKind",
+                            Variant::$variant as u32,
+                            stringify!($variant),
+                            v,
+                        ),
+                    )*
+                    Self::__Unknown(unknown) => Err(ser::Error::custom(format_args!(
+                        "cannot serialize unknown effect type {:?}",
+                        unknown.type_,
+                    ))),
+                }
+            }
+        }
+
+        /// Deserialization of effects with a built-in [`Waveform`].
+        ///
+        /// Like [`Periodic`], an [`EffectKind`] that contains a custom waveform cannot be
+        /// deserialized.
+        impl<'de> Deserialize<'de> for EffectKind<'static> {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                struct Visitor;
+
+                impl<'de> de::Visitor<'de> for Visitor {
+                    type Value = EffectKind<'static>;
+
+                    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                        formatter.write_str("enum EffectKind")
+                    }
+
+                    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+                    where
+                        A: EnumAccess<'de>,
+                    {
+                        let (variant, access) = data.variant::<Variant>()?;
+                        match variant {
+                            $(
+                                Variant::$variant => {
+                                    access.newtype_variant().map(EffectKind::$variant)
+                                }
+                            )*
+                        }
+                    }
+                }
+
+                deserializer.deserialize_enum("EffectKind", VARIANTS, Visitor)
+            }
+        }
+    };
+}
+
+effect_kind_impls!(
+    Constant, Ramp, Periodic, Rumble, Spring, Friction, Damper, Inertia
+);
+
+#[cfg(test)]
+mod tests {
+    use csv::{ReaderBuilder, WriterBuilder};
+
+    use super::*;
+
+    fn roundtrip<T>(value: &T) -> T
+    where
+        T: Serialize + for<'de> Deserialize<'de>,
+    {
+        let bytes = postcard::to_allocvec(value).unwrap();
+        postcard::from_bytes(&bytes).unwrap()
+    }
+
+    #[test]
+    fn postcard() {
+        let env = Envelope::new().with_attack_length(100).with_fade_level(5);
+        let kinds = [
+            EffectKind::Constant(Constant::new(-100).with_envelope(env)),
+            EffectKind::Ramp(Ramp::new(0, 1000)),
+            EffectKind::Periodic(Periodic::simple(Waveform::SINE, 50, 1234).with_phase(9)),
+            EffectKind::Rumble(Rumble::new(0xffff, 0x1000)),
+            EffectKind::Spring([Spring::new(Condition::new().with_center(3)); 2]),
+            EffectKind::Inertia([
+                Inertia::new(Condition::new().with_deadband(1)),
+                Inertia::new(Condition::new().with_left_coeff(-7)),
+            ]),
+        ];
+        for kind in kinds {
+            assert_eq!(roundtrip(&kind), kind);
+        }
+
+        let trigger = Trigger::new(Key::BTN_TRIGGER, 250);
+        assert_eq!(roundtrip(&trigger), trigger);
+        let replay = Replay::new(1000, 20);
+        assert_eq!(roundtrip(&replay), replay);
+        assert_eq!(
+            roundtrip(&Waveform::from_raw(0x1234)),
+            Waveform::from_raw(0x1234)
+        );
+    }
+
+    #[test]
+    fn custom_waveform() {
+        let owned = Periodic::custom_owned(vec![0, 100, 0, -100]).with_offset(4);
+        let bytes = postcard::to_allocvec(&owned).unwrap();
+        assert_eq!(
+            postcard::from_bytes::<OwnedPeriodic>(&bytes).unwrap(),
+            owned
+        );
+        // The borrowing types can be serialized, and produce the same representation.
+        assert_eq!(postcard::to_allocvec(&owned.as_periodic()).unwrap(), bytes);
+        postcard::from_bytes::<Periodic<'static>>(&bytes).unwrap_err();
+
+        let bytes = postcard::to_allocvec(&EffectKind::Periodic(owned.as_periodic())).unwrap();
+        postcard::from_bytes::<EffectKind<'static>>(&bytes).unwrap_err();
+
+        // Built-in waveforms can also be deserialized as `OwnedPeriodic`.
+        let simple = Periodic::simple(Waveform::SQUARE, 10, 20);
+        let owned = roundtrip::<OwnedPeriodic>(&Periodic::simple(Waveform::SQUARE, 10, 20).into());
+        assert_eq!(owned.as_periodic(), simple);
+        assert!(owned.data().is_empty());
+    }
+
+    #[test]
+    fn csv() {
+        let mut out = Vec::new();
+        let mut w = WriterBuilder::new().from_writer(&mut out);
+        w.serialize(Rumble::new(1000, 2000)).unwrap();
+        w.serialize(Rumble::new(0, 5)).unwrap();
+        w.flush().unwrap();
+        drop(w);
+
+        let s = String::from_utf8(out).unwrap();
+        assert_eq!(s, "strong_magnitude,weak_magnitude\n1000,2000\n0,5\n");
+
+        let mut r = ReaderBuilder::new().from_reader(s.as_bytes());
+        let rumbles = r
+            .deserialize::<Rumble>()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(rumbles, [Rumble::new(1000, 2000), Rumble::new(0, 5)]);
+
+        // Unknown and missing fields are rejected.
+        let mut r = ReaderBuilder::new().from_reader("strong_magnitude,extra\n1,2\n".as_bytes());
+        r.deserialize::<Rumble>().next().unwrap().unwrap_err();
+        let mut r = ReaderBuilder::new().from_reader("strong_magnitude\n1\n".as_bytes());
+        r.deserialize::<Rumble>().next().unwrap().unwrap_err();
+
+        // The envelope is optional.
+        let mut r = ReaderBuilder::new().from_reader("level\n-5\n".as_bytes());
+        let constant = r.deserialize::<Constant>().next().unwrap().unwrap();
+        assert_eq!(constant, Constant::new(-5));
+    }
+}
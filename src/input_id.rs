@@ -57,6 +57,181 @@ impl InputId {
     pub fn version(&self) -> u16 {
         self.0.version
     }
+
+    /// Formats the vendor and product ID in the `vvvv:pppp` hex format used by `lsusb` and other
+    /// tools.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use evdevil::{Bus, InputId};
+    ///
+    /// let id = InputId::new(Bus::USB, 0x046d, 0xc52b, 0x0111);
+    /// assert_eq!(id.vendor_product_string(), "046d:c52b");
+    /// ```
+    pub fn vendor_product_string(&self) -> String {
+        format!("{:04x}:{:04x}", self.vendor(), self.product())
+    }
+
+    /// Returns the vendor and product ID as a tuple.
+    ///
+    /// This is the key typically used by product databases like the USB ID registry.
+    #[inline]
+    pub fn vendor_product(&self) -> (u16, u16) {
+        (self.vendor(), self.product())
+    }
+
+    /// Looks up a human-readable product name in a user-supplied database.
+    ///
+    /// `db` is called with the vendor and product ID of this device.
+    /// This crate does not ship a product database; this method only standardizes how one is
+    /// queried.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use evdevil::{Bus, InputId};
+    ///
+    /// fn usb_db(vendor: u16, product: u16) -> Option<&'static str> {
+    ///     match (vendor, product) {
+    ///         (0x045e, 0x028e) => Some("Xbox 360 Controller"),
+    ///         _ => None,
+    ///     }
+    /// }
+    ///
+    /// let id = InputId::new(Bus::USB, 0x045e, 0x028e, 0x0110);
+    /// assert_eq!(id.lookup_name(usb_db), Some("Xbox 360 Controller"));
+    /// ```
+    pub fn lookup_name<'a>(&self, db: impl FnOnce(u16, u16) -> Option<&'a str>) -> Option<&'a str> {
+        db(self.vendor(), self.product())
+    }
+
+    /// Returns whether this [`InputId`] matches `pattern`.
+    ///
+    /// Fields that `pattern` has wildcarded are ignored; all other fields have to be equal.
+    pub fn matches(&self, pattern: &InputIdPattern) -> bool {
+        pattern.bus.is_none_or(|bus| bus == self.bus())
+            && pattern.vendor.is_none_or(|vendor| vendor == self.vendor())
+            && pattern
+                .product
+                .is_none_or(|product| product == self.product())
+            && pattern
+                .version
+                .is_none_or(|version| version == self.version())
+    }
+}
+
+/// A pattern matching [`InputId`]s, with optional wildcards for individual fields.
+///
+/// This is useful for looking up per-device configuration, where the device version is often not
+/// relevant.
+/// Patterns are checked with [`InputId::matches`].
+///
+/// # Example
+///
+/// ```
+/// use evdevil::{Bus, InputId, InputIdPattern};
+///
+/// let pattern = InputIdPattern::new(InputId::new(Bus::USB, 0x045e, 0x028e, 0x0110)).any_version();
+///
+/// assert!(InputId::new(Bus::USB, 0x045e, 0x028e, 0x0114).matches(&pattern));
+/// assert!(!InputId::new(Bus::BLUETOOTH, 0x045e, 0x028e, 0x0114).matches(&pattern));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InputIdPattern {
+    bus: Option<Bus>,
+    vendor: Option<u16>,
+    product: Option<u16>,
+    version: Option<u16>,
+}
+
+impl InputIdPattern {
+    /// Creates a pattern that only matches [`InputId`]s equal to `id`.
+    ///
+    /// Individual fields can then be turned into wildcards with the `any_*` methods.
+    #[inline]
+    pub const fn new(id: InputId) -> Self {
+        Self {
+            bus: Some(Bus(id.0.bustype)),
+            vendor: Some(id.0.vendor),
+            product: Some(id.0.product),
+            version: Some(id.0.version),
+        }
+    }
+
+    /// Creates a pattern that matches every [`InputId`].
+    #[inline]
+    pub const fn any() -> Self {
+        Self {
+            bus: None,
+            vendor: None,
+            product: None,
+            version: None,
+        }
+    }
+
+    /// Makes the pattern match any [`Bus`].
+    #[inline]
+    pub const fn any_bus(mut self) -> Self {
+        self.bus = None;
+        self
+    }
+
+    /// Makes the pattern match any vendor ID.
+    #[inline]
+    pub const fn any_vendor(mut self) -> Self {
+        self.vendor = None;
+        self
+    }
+
+    /// Makes the pattern match any product ID.
+    #[inline]
+    pub const fn any_product(mut self) -> Self {
+        self.product = None;
+        self
+    }
+
+    /// Makes the pattern match any device version.
+    #[inline]
+    pub const fn any_version(mut self) -> Self {
+        self.version = None;
+        self
+    }
+
+    /// Makes the pattern only match [`InputId`]s with the given [`Bus`].
+    #[inline]
+    pub const fn with_bus(mut self, bus: Bus) -> Self {
+        self.bus = Some(bus);
+        self
+    }
+
+    /// Makes the pattern only match [`InputId`]s with the given vendor ID.
+    #[inline]
+    pub const fn with_vendor(mut self, vendor: u16) -> Self {
+        self.vendor = Some(vendor);
+        self
+    }
+
+    /// Makes the pattern only match [`InputId`]s with the given product ID.
+    #[inline]
+    pub const fn with_product(mut self, product: u16) -> Self {
+        self.product = Some(product);
+        self
+    }
+
+    /// Makes the pattern only match [`InputId`]s with the given device version.
+    #[inline]
+    pub const fn with_version(mut self, version: u16) -> Self {
+        self.version = Some(version);
+        self
+    }
+}
+
+impl From<InputId> for InputIdPattern {
+    #[inline]
+    fn from(id: InputId) -> Self {
+        Self::new(id)
+    }
 }
 
 impl fmt::Debug for InputId {
@@ -127,6 +302,36 @@ mod tests {
         assert_eq!(format!("{:?}", Bus(0xffff)), "Bus(0xffff)");
     }
 
+    #[test]
+    fn pattern() {
+        let id = InputId::new(Bus::USB, 0x1234, 0x5678, 0x0102);
+        assert!(id.matches(&InputIdPattern::new(id)));
+        assert!(id.matches(&InputIdPattern::any()));
+
+        let newer = InputId::new(Bus::USB, 0x1234, 0x5678, 0x0103);
+        assert!(!newer.matches(&InputIdPattern::new(id)));
+        assert!(newer.matches(&InputIdPattern::new(id).any_version()));
+
+        let bt = InputId::new(Bus::BLUETOOTH, 0x1234, 0x5678, 0x0102);
+        assert!(!bt.matches(&InputIdPattern::new(id)));
+        assert!(bt.matches(&InputIdPattern::new(id).any_bus()));
+
+        let pattern = InputIdPattern::any().with_vendor(0x1234);
+        assert!(id.matches(&pattern));
+        assert!(!InputId::new(Bus::USB, 0x4321, 0x5678, 0x0102).matches(&pattern));
+    }
+
+    #[test]
+    fn vendor_product() {
+        let id = InputId::new(Bus::USB, 0x1234, 0x5678, 0x0102);
+        assert_eq!(id.vendor_product(), (0x1234, 0x5678));
+        assert_eq!(id.lookup_name(|_, _| None), None);
+        assert_eq!(
+            id.lookup_name(|v, p| (v == 0x1234 && p == 0x5678).then_some("Widget")),
+            Some("Widget")
+        );
+    }
+
     #[test]
     fn input_id_debug() {
         assert_eq!(
@@ -10,9 +10,12 @@ use crate::{event::Key, raw::input::input_keymap_entry};
 /// which are defined as a US layout.
 ///
 /// Returned by [`Evdev::keymap_entry`] and [`Evdev::keymap_entry_by_index`].
+/// Can also be created via [`KeymapEntry::new`] and written to a device with
+/// [`Evdev::set_keymap_entry_full`], which allows copying a keymap from one device to another.
 ///
 /// [`Evdev::keymap_entry`]: crate::Evdev::keymap_entry
 /// [`Evdev::keymap_entry_by_index`]: crate::Evdev::keymap_entry_by_index
+/// [`Evdev::set_keymap_entry_full`]: crate::Evdev::set_keymap_entry_full
 #[derive(Clone, Copy)]
 #[repr(transparent)]
 pub struct KeymapEntry(pub(crate) input_keymap_entry);
@@ -23,6 +26,17 @@ impl KeymapEntry {
         unsafe { mem::zeroed() }
     }
 
+    /// Creates a [`KeymapEntry`] that maps `scancode` to `keycode`.
+    ///
+    /// The [`KeymapEntry::index`] of the returned entry is 0.
+    pub fn new(scancode: Scancode, keycode: Key) -> Self {
+        let mut entry = Self::zeroed();
+        entry.0.keycode = keycode.raw().into();
+        entry.0.len = scancode.len;
+        entry.0.scancode = scancode.bytes;
+        entry
+    }
+
     /// Zero-based index of this entry in the keymap.
     #[inline]
     pub fn index(&self) -> u16 {
@@ -199,4 +213,12 @@ mod tests {
         let code = Scancode::from(0x1000u16);
         assert_eq!(format!("{code}"), "1000");
     }
+
+    #[test]
+    fn new_entry() {
+        let entry = KeymapEntry::new(Scancode::from(0x7001eu32), Key::KEY_1);
+        assert_eq!(entry.index(), 0);
+        assert_eq!(entry.keycode(), Key::KEY_1);
+        assert_eq!(entry.scancode().to_string(), "7001e");
+    }
 }
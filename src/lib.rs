@@ -12,6 +12,7 @@ mod macros;
 mod test;
 
 mod abs_info;
+mod backend_caps;
 mod batch;
 pub mod bits;
 mod drop;
@@ -23,6 +24,7 @@ pub mod hotplug;
 mod input_id;
 mod key_repeat;
 mod keymap_entry;
+mod pointer_caps;
 mod raw;
 pub mod reader;
 mod slot;
@@ -37,19 +39,21 @@ use std::{
 };
 
 pub use abs_info::AbsInfo;
+pub use backend_caps::BackendCaps;
 #[doc(inline)]
 pub use enumerate::{enumerate, enumerate_hotplug};
 pub use evdev::*;
 pub use event::codes::{InputProp, ParseVariantError};
-pub use input_id::{Bus, InputId};
+pub use input_id::{Bus, InputId, InputIdPattern};
 pub use key_repeat::KeyRepeat;
 pub use keymap_entry::{KeymapEntry, Scancode};
+pub use pointer_caps::PointerCaps;
 #[doc(inline)]
 pub use reader::EventReader;
 pub use slot::Slot;
 pub use version::Version;
 
-use crate::event::InputEvent;
+use crate::event::{EventType, InputEvent, Syn};
 
 /// Reads raw events from an `Evdev` or a `UinputDevice` (`file`) into `dest`.
 ///
@@ -64,7 +68,14 @@ fn read_raw(mut file: &File, dest: &mut [InputEvent]) -> io::Result<usize> {
 }
 
 /// Writes all events from `events` to an `Evdev` or a `UinputDevice` represented by `File`.
+///
+/// In debug builds, this panics if `events` contains an event that never makes sense to write to
+/// a device (`EV_UINPUT` events, which are only sent *by* the kernel, and `SYN_DROPPED`).
 fn write_raw(mut file: &File, events: &[InputEvent]) -> io::Result<()> {
+    debug_assert!(
+        events.iter().all(|ev| is_writable(*ev)),
+        "attempted to write an `EV_UINPUT` or `SYN_DROPPED` event to a device: {events:?}"
+    );
     let bptr = events.as_ptr().cast::<u8>();
     // Safety: this requires that `InputEvent` contains no padding, which is tested where `input_event` is defined.
     let bytes = unsafe { slice::from_raw_parts(bptr, size_of::<InputEvent>() * events.len()) };
@@ -72,18 +83,45 @@ fn write_raw(mut file: &File, events: &[InputEvent]) -> io::Result<()> {
     Ok(())
 }
 
+/// Returns whether `event` may be written to an `Evdev` or `UinputDevice`.
+fn is_writable(event: InputEvent) -> bool {
+    match event.event_type() {
+        EventType::UINPUT => false,
+        EventType::SYN => event.raw_code() != Syn::DROPPED.raw(),
+        _ => true,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{hotplug::HotplugMonitor, uinput::UinputDevice};
 
     use super::*;
 
+    #[test]
+    fn writable_events() {
+        assert!(is_writable(Syn::REPORT.into()));
+        assert!(is_writable(InputEvent::new(EventType::LED, 0, 1)));
+        assert!(!is_writable(Syn::DROPPED.into()));
+        assert!(!is_writable(InputEvent::new(EventType::UINPUT, 1, 0)));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic = "SYN_DROPPED"]
+    fn write_dropped() {
+        let file = File::options().write(true).open("/dev/null").unwrap();
+        write_raw(&file, &[Syn::DROPPED.into()]).ok();
+    }
+
     #[test]
     fn send_sync() {
         fn assert<T: Send + Sync>() {}
 
         assert::<Evdev>();
         assert::<EventReader>();
+        assert::<reader::SlotSnapshot>();
+        assert::<reader::InputState>();
         assert::<UinputDevice>();
         assert::<HotplugMonitor>();
     }
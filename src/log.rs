@@ -59,3 +59,65 @@ mod log {
         }};
     }
 }
+
+#[macro_use]
+#[cfg(feature = "tracing")]
+mod span {
+    /// Creates and enters a debug-level `tracing` span for an operation on an `Evdev`.
+    ///
+    /// The span has the device's file descriptor and name as fields, and records the time the
+    /// operation took in its `elapsed` field when the returned guard is dropped.
+    macro_rules! device_span {
+        ($name:literal, $evdev:expr $(, $($fields:tt)+)?) => {{
+            let evdev: &$crate::Evdev = $evdev;
+            $crate::log::TimedSpan::new(::tracing::debug_span!(
+                $name,
+                fd = ::std::os::fd::AsRawFd::as_raw_fd(evdev),
+                device = evdev.name().unwrap_or_default(),
+                elapsed = ::tracing::field::Empty,
+                $($($fields)+)?
+            ))
+        }};
+    }
+}
+
+#[macro_use]
+#[cfg(not(feature = "tracing"))]
+mod span {
+    /// Without `tracing`, the time the operation took is logged when the returned guard is
+    /// dropped.
+    macro_rules! device_span {
+        ($name:literal, $evdev:expr $(, $($fields:tt)+)?) => {{
+            let _: &$crate::Evdev = $evdev;
+            let start = ::std::time::Instant::now();
+            $crate::drop::on_drop(move || {
+                debug!(concat!("`", $name, "` took {:?}"), start.elapsed())
+            })
+        }};
+    }
+}
+
+/// Guard returned by `device_span!`.
+#[cfg(feature = "tracing")]
+pub(crate) struct TimedSpan {
+    span: tracing::span::EnteredSpan,
+    start: std::time::Instant,
+}
+
+#[cfg(feature = "tracing")]
+impl TimedSpan {
+    pub(crate) fn new(span: tracing::Span) -> Self {
+        Self {
+            span: span.entered(),
+            start: std::time::Instant::now(),
+        }
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl Drop for TimedSpan {
+    fn drop(&mut self) {
+        let elapsed = self.start.elapsed();
+        self.span.record("elapsed", tracing::field::debug(elapsed));
+    }
+}
@@ -31,6 +31,33 @@ macro_rules! ffi_enum {
                 }
             }
 
+            /// All variants and their names, in declaration order.
+            #[allow(dead_code)]
+            const VARIANTS: &'static [(&'static str, Self)] = &[
+                $( (stringify!($variant), Self::$variant), )+
+            ];
+
+            /// Returns the names of all variants with the same value as `self`.
+            #[allow(dead_code)]
+            pub(crate) fn variant_names(self) -> impl Iterator<Item = &'static str> {
+                Self::VARIANTS
+                    .iter()
+                    .filter(move |(_, value)| *value == self)
+                    .map(|(name, _)| *name)
+            }
+
+            /// Returns an iterator over all named constants of this type, in declaration order.
+            ///
+            /// Constants that are aliases of an earlier constant (with the same value) are
+            /// skipped, so every value is yielded only once.
+            #[allow(dead_code)]
+            $v fn all_named() -> impl Iterator<Item = Self> {
+                Self::VARIANTS
+                    .iter()
+                    .filter(|(name, value)| value.variant_name() == Some(*name))
+                    .map(|(_, value)| *value)
+            }
+
             #[allow(dead_code)]
             pub(crate) fn from_variant_name(name: &str) -> Option<Self> {
                 match name {
@@ -76,5 +103,29 @@ macro_rules! bitvalue {
         impl $crate::bits::BitValue for $type {
             const MAX: Self = <Self>::MAX;
         }
+        impl $crate::bits::BitSet<$type> {
+            #[doc = concat!("Creates a [`BitSet`][crate::bits::BitSet] containing the given [`", stringify!($type), "`]s.")]
+            ///
+            /// Unlike [`FromIterator`], this is a `const fn`, so it can be used to define
+            /// capability sets as constants.
+            ///
+            /// # Panics
+            ///
+            /// Panics (or fails to compile, when used in a `const` context) if any value is greater
+            /// than [`BitValue::MAX`][crate::bits::BitValue::MAX].
+            pub const fn from_slice(values: &[$type]) -> Self {
+                let mut words = <$type as $crate::bits::BitValueImpl>::__PRIVATE_ZERO;
+                let mut i = 0;
+                while i < values.len() {
+                    assert!(values[i].0 <= <$type>::MAX.0, "value out of range for `BitSet`");
+                    let index = values[i].0 as usize;
+                    let wordpos = index / $crate::bits::Word::BITS as usize;
+                    let bitpos = index % $crate::bits::Word::BITS as usize;
+                    words[wordpos] |= 1 << bitpos;
+                    i += 1;
+                }
+                Self { words }
+            }
+        }
     };
 }
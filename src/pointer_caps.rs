@@ -0,0 +1,87 @@
+use std::fmt;
+
+use crate::{bits::BitSet, event::Rel};
+
+/// Pointer-related capabilities of a device.
+///
+/// Returned by [`Evdev::pointer_caps`].
+///
+/// This interprets the set of supported [`Rel`] axes for mouse-like devices.
+///
+/// [`Evdev::pointer_caps`]: crate::Evdev::pointer_caps
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct PointerCaps {
+    rel: BitSet<Rel>,
+}
+
+impl PointerCaps {
+    pub(crate) fn new(rel: BitSet<Rel>) -> Self {
+        Self { rel }
+    }
+
+    /// Returns whether the device reports horizontal motion ([`Rel::X`]).
+    #[inline]
+    pub fn has_x(&self) -> bool {
+        self.rel.contains(Rel::X)
+    }
+
+    /// Returns whether the device reports vertical motion ([`Rel::Y`]).
+    #[inline]
+    pub fn has_y(&self) -> bool {
+        self.rel.contains(Rel::Y)
+    }
+
+    /// Returns whether the device has a vertical scroll wheel ([`Rel::WHEEL`]).
+    #[inline]
+    pub fn has_wheel(&self) -> bool {
+        self.rel.contains(Rel::WHEEL)
+    }
+
+    /// Returns whether the device has a horizontal scroll wheel ([`Rel::HWHEEL`]).
+    #[inline]
+    pub fn has_hwheel(&self) -> bool {
+        self.rel.contains(Rel::HWHEEL)
+    }
+
+    /// Returns whether the device reports high-resolution scroll events ([`Rel::WHEEL_HI_RES`]
+    /// or [`Rel::HWHEEL_HI_RES`]).
+    ///
+    /// High-resolution scroll events are sent in addition to the regular [`Rel::WHEEL`] and
+    /// [`Rel::HWHEEL`] events, in units of 1/120th of a notch.
+    #[inline]
+    pub fn has_hi_res(&self) -> bool {
+        self.rel.contains(Rel::WHEEL_HI_RES) || self.rel.contains(Rel::HWHEEL_HI_RES)
+    }
+}
+
+impl fmt::Debug for PointerCaps {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PointerCaps")
+            .field("has_x", &self.has_x())
+            .field("has_y", &self.has_y())
+            .field("has_wheel", &self.has_wheel())
+            .field("has_hwheel", &self.has_hwheel())
+            .field("has_hi_res", &self.has_hi_res())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn caps() {
+        let caps = PointerCaps::new(BitSet::from_iter([Rel::X, Rel::Y, Rel::WHEEL]));
+        assert!(caps.has_x());
+        assert!(caps.has_y());
+        assert!(caps.has_wheel());
+        assert!(!caps.has_hwheel());
+        assert!(!caps.has_hi_res());
+
+        let caps = PointerCaps::new(BitSet::from_iter([Rel::HWHEEL, Rel::HWHEEL_HI_RES]));
+        assert!(!caps.has_x());
+        assert!(caps.has_hwheel());
+        assert!(caps.has_hi_res());
+    }
+}
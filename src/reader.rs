@@ -1,11 +1,18 @@
 //! A convenient API for robustly reading device events.
 
 mod r#async;
+mod changes;
+mod merge;
+mod state;
 
 #[cfg_attr(docsrs, doc(cfg(any(feature = "tokio", feature = "async-io"))))]
 #[cfg(any(feature = "tokio", feature = "async-io"))]
 pub use r#async::{AsyncEvents, AsyncReports};
 
+pub use changes::{ChangeFilter, ReportsChanging};
+pub use merge::Merge;
+pub use state::InputState;
+
 #[cfg(test)]
 mod tests;
 
@@ -13,26 +20,28 @@ use std::{
     collections::VecDeque,
     fmt, io,
     iter::{self, FusedIterator, zip},
-    ops::RangeInclusive,
+    mem::ManuallyDrop,
+    ops::{Deref, DerefMut, RangeInclusive},
     os::fd::{AsFd, AsRawFd, BorrowedFd, IntoRawFd, RawFd},
     slice,
     sync::Arc,
-    time::{Instant, SystemTime},
+    task::{Context, Poll},
+    time::{Duration, Instant, SystemTime},
 };
 
 use crate::{
-    Evdev, Slot,
+    Evdev, Scancode, Slot,
     bits::{BitSet, BitValue},
-    drop::on_drop,
     event::{
         Abs, AbsEvent, EventKind, EventType, InputEvent, Key, KeyEvent, KeyState, Led, LedEvent,
-        Sound, SoundEvent, Switch, SwitchEvent, Syn, SynEvent,
+        Misc, Sound, SoundEvent, Switch, SwitchEvent, Syn, SynEvent,
     },
     raw::input::EVIOCGMTSLOTS,
-    read_raw,
+    read_raw, util,
 };
 
-const MAX_MT_SLOTS: i32 = 60;
+/// Upper bound on the number of multitouch slots that are tracked or queried.
+pub(crate) const MAX_MT_SLOTS: i32 = 60;
 
 /// Storage for the current multitouch state.
 #[derive(Clone, PartialEq)]
@@ -259,6 +268,16 @@ impl MtStorage {
         })
     }
 
+    fn slot_state(&self, slot: impl TryInto<Slot>, code: Abs) -> Option<i32> {
+        assert!(
+            code.raw() > Abs::MT_SLOT.raw(),
+            "`slot_state` requires an `ABS_MT_*` value above `ABS_MT_SLOT`"
+        );
+
+        let slot: Slot = slot.try_into().ok()?;
+        self.group_for_code(code)?.get(slot.raw() as usize).copied()
+    }
+
     /// Iterator over all slot indices with valid data in them.
     fn valid_slots(&self) -> ValidSlots<'_> {
         ValidSlots {
@@ -301,6 +320,50 @@ impl<'a> FusedIterator for ValidSlots<'a> {
     // `slice::Iter` is fused, therefore `iter::Enumerate<I>` is fused.
 }
 
+/// An owned copy of the multitouch state of an [`EventReader`].
+///
+/// Returned by [`EventReader::slot_snapshot`] and [`Evdev::mt_slot_state`].
+///
+/// Unlike the [`EventReader`] itself, a [`SlotSnapshot`] does not change as events are read, so it
+/// can be kept around to compare it to the state at a later point in time, or sent to another
+/// thread.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlotSnapshot {
+    mt_storage: MtStorage,
+}
+
+impl SlotSnapshot {
+    /// Fetches the current multitouch state of `evdev`.
+    pub(crate) fn current(evdev: &Evdev) -> io::Result<Self> {
+        Ok(Self {
+            mt_storage: MtStorage::current(evdev, &evdev.supported_abs_axes()?)?,
+        })
+    }
+
+    /// Returns an iterator that yields all multitouch [`Slot`]s that had valid data in them.
+    ///
+    /// See [`EventReader::valid_slots`].
+    #[inline]
+    pub fn slots(&self) -> ValidSlots<'_> {
+        self.mt_storage.valid_slots()
+    }
+
+    /// Returns the value of an [`Abs`] axis in a multitouch slot.
+    ///
+    /// See [`EventReader::slot_state`] for the conditions under which this returns [`None`] or
+    /// panics.
+    #[inline]
+    pub fn get(&self, slot: impl TryInto<Slot>, code: Abs) -> Option<i32> {
+        self.mt_storage.slot_state(slot, code)
+    }
+
+    /// Returns the multitouch slot that was selected when the snapshot was taken.
+    #[inline]
+    pub fn current_slot(&self) -> Slot {
+        Slot::from_raw(self.mt_storage.active_slot as i32)
+    }
+}
+
 #[derive(Debug)]
 struct DeviceState {
     keys: BitSet<Key>,
@@ -308,7 +371,14 @@ struct DeviceState {
     sounds: BitSet<Sound>,
     switches: BitSet<Switch>,
     abs: [i32; Abs::MT_SLOT.raw() as usize],
+    /// Smallest and largest value seen in the event stream for each axis.
+    abs_observed: [Option<(i32, i32)>; Abs::MT_SLOT.raw() as usize],
     abs_axes: BitSet<Abs>, // supported axes
+    /// Whether the device supports `ABS_MT_SLOT`.
+    ///
+    /// Computed once on construction, so that devices without multitouch support (keyboards, mice,
+    /// etc.) skip all multitouch handling when processing events and resyncing.
+    has_mt: bool,
     mt_storage: MtStorage,
     last_event: SystemTime,
 }
@@ -322,6 +392,8 @@ impl DeviceState {
             sounds: BitSet::new(),
             switches: BitSet::new(),
             abs: [0; Abs::MT_SLOT.raw() as usize],
+            abs_observed: [None; Abs::MT_SLOT.raw() as usize],
+            has_mt: abs_axes.contains(Abs::MT_SLOT),
             abs_axes,
             mt_storage: MtStorage::empty(),
             // We emit events to update to the current device state, but without having any device
@@ -347,14 +419,23 @@ impl DeviceState {
             }
         }
 
+        let has_mt = abs_axes.contains(Abs::MT_SLOT);
+        let mt_storage = if has_mt {
+            MtStorage::current(evdev, &abs_axes)?
+        } else {
+            MtStorage::empty()
+        };
+
         Ok(Self {
             keys: evdev.key_state()?,
             leds: evdev.led_state()?,
             sounds: evdev.sound_state()?,
             switches: evdev.switch_state()?,
             abs,
+            abs_observed: [None; Abs::MT_SLOT.raw() as usize],
             abs_axes,
-            mt_storage: MtStorage::current(evdev, &abs_axes)?,
+            has_mt,
+            mt_storage,
             last_event: SystemTime::now(),
         })
     }
@@ -404,7 +485,7 @@ impl DeviceState {
             }
         }
 
-        if self.abs_axes.contains(Abs::MT_SLOT) {
+        if self.has_mt {
             // Re-fetch the state of every MT slot
             self.mt_storage
                 .resync_from(&src.mt_storage, queue, self.last_event);
@@ -432,8 +513,7 @@ impl DeviceState {
     ///
     /// - `queue` will either be empty, or its last element will be a SYN_REPORT.
     fn resync(&mut self, evdev: &Evdev, queue: &mut VecDeque<InputEvent>) -> io::Result<()> {
-        let now = Instant::now();
-        let _d = on_drop(|| debug!("`EventReader::resync` took {:?}", now.elapsed()));
+        let _span = device_span!("EventReader::resync", evdev);
 
         // Clear out all events, and drain the kernel buffer too, like libevdev does.
         let mut reads = 0;
@@ -458,7 +538,15 @@ impl DeviceState {
         match ev.kind() {
             EventKind::Abs(ev) => {
                 if ev.abs().raw() < Abs::MT_SLOT.raw() {
-                    self.abs[ev.abs().raw() as usize] = ev.value();
+                    let index = ev.abs().raw() as usize;
+                    self.abs[index] = ev.value();
+                    let observed = &mut self.abs_observed[index];
+                    *observed = Some(match *observed {
+                        Some((min, max)) => (min.min(ev.value()), max.max(ev.value())),
+                        None => (ev.value(), ev.value()),
+                    });
+                } else if !self.has_mt {
+                    // Not a multitouch device, nothing to update.
                 } else if ev.abs() == Abs::MT_SLOT {
                     self.mt_storage.active_slot = ev.value() as u32;
                 } else {
@@ -471,40 +559,34 @@ impl DeviceState {
                 }
             }
             EventKind::Key(ev) => match ev.state() {
-                KeyState::PRESSED => {
-                    self.keys.insert(ev.key());
-                }
-                KeyState::RELEASED => {
-                    self.keys.remove(ev.key());
-                }
+                KeyState::PRESSED => set_bit(&mut self.keys, ev.key(), true),
+                KeyState::RELEASED => set_bit(&mut self.keys, ev.key(), false),
                 _ => {}
             },
-            EventKind::Led(ev) => {
-                if ev.is_on() {
-                    self.leds.insert(ev.led());
-                } else {
-                    self.leds.remove(ev.led());
-                }
-            }
-            EventKind::Switch(ev) => {
-                if ev.is_pressed() {
-                    self.switches.insert(ev.switch());
-                } else {
-                    self.switches.remove(ev.switch());
-                }
-            }
-            EventKind::Sound(ev) => {
-                if ev.is_playing() {
-                    self.sounds.insert(ev.sound());
-                } else {
-                    self.sounds.remove(ev.sound());
-                }
-            }
+            EventKind::Led(ev) => set_bit(&mut self.leds, ev.led(), ev.is_on()),
+            EventKind::Switch(ev) => set_bit(&mut self.switches, ev.switch(), ev.is_pressed()),
+            EventKind::Sound(ev) => set_bit(&mut self.sounds, ev.sound(), ev.is_playing()),
             _ => {}
         }
     }
 }
 
+/// Sets or clears `value` in `set`, ignoring values that are out of range.
+///
+/// The kernel never emits such values, but a misbehaving device (or a test) shouldn't be able to
+/// make the [`EventReader`] panic.
+fn set_bit<V: BitValue + fmt::Debug>(set: &mut BitSet<V>, value: V, on: bool) {
+    if value.into_index() > V::MAX.into_index() {
+        warn!("ignoring out-of-range event code {value:?}");
+        return;
+    }
+    if on {
+        set.insert(value);
+    } else {
+        set.remove(value);
+    }
+}
+
 /// Sans-I/O portion of the [`EventReader`] implementation.
 #[derive(Debug)]
 struct Impl {
@@ -526,6 +608,16 @@ struct Impl {
     ///
     /// Set after we get a `SYN_DROPPED` to clear out incomplete reports.
     discard_events: bool,
+    /// Number of `SYN_DROPPED` events received so far.
+    overflow_count: u64,
+    /// Copies of the most recently consumed reports, oldest first.
+    history: VecDeque<Report>,
+    /// Maximum number of reports kept in `history`.
+    history_len: usize,
+    /// How long to wait for a missing `SYN_REPORT` before synthesizing one.
+    syn_timeout: Option<Duration>,
+    /// When `refill` first saw the currently incomplete report, in non-blocking mode.
+    incomplete_since: Option<Instant>,
 }
 
 impl Impl {
@@ -535,6 +627,11 @@ impl Impl {
             incoming: Arc::default(),
             skip: 0,
             discard_events: false,
+            overflow_count: 0,
+            history: VecDeque::new(),
+            history_len: 0,
+            syn_timeout: None,
+            incomplete_since: None,
         }
     }
 
@@ -542,22 +639,25 @@ impl Impl {
         self.state.abs[abs.raw() as usize]
     }
 
+    fn try_abs_state(&self, abs: Abs) -> Option<i32> {
+        if abs.raw() < Abs::MT_SLOT.raw() {
+            Some(self.abs_state(abs))
+        } else if abs == Abs::MT_SLOT {
+            self.state
+                .abs_axes
+                .contains(Abs::MT_SLOT)
+                .then(|| self.current_slot().raw())
+        } else {
+            self.slot_state(self.current_slot(), abs)
+        }
+    }
+
     fn valid_slots(&self) -> ValidSlots<'_> {
         self.state.mt_storage.valid_slots()
     }
 
     fn slot_state(&self, slot: impl TryInto<Slot>, code: Abs) -> Option<i32> {
-        assert!(
-            code.raw() > Abs::MT_SLOT.raw(),
-            "`slot_state` requires an `ABS_MT_*` value above `ABS_MT_SLOT`"
-        );
-
-        let slot: Slot = slot.try_into().ok()?;
-        self.state
-            .mt_storage
-            .group_for_code(code)?
-            .get(slot.raw() as usize)
-            .copied()
+        self.state.mt_storage.slot_state(slot, code)
     }
     fn current_slot(&self) -> Slot {
         Slot::from_raw(self.state.mt_storage.active_slot as i32)
@@ -569,7 +669,72 @@ impl Impl {
         Arc::make_mut(&mut self.incoming).drain(..self.skip);
         self.skip = 0;
     }
+    /// Discards all queued events and resynchronizes the device state.
+    fn reset(&mut self, iface: &impl Interface) -> io::Result<()> {
+        let incoming = Arc::make_mut(&mut self.incoming);
+        incoming.clear();
+        self.skip = 0;
+        self.discard_events = false;
+        iface.resync(&mut self.state, incoming)
+    }
+
     fn next_report(&mut self, iface: &mut impl Interface) -> io::Result<Report> {
+        let report = self.peek_report(iface)?;
+
+        self.incoming
+            .range(report.range.clone())
+            .for_each(|ev| self.state.update_state(*ev));
+        self.skip = *report.range.end() + 1;
+        self.record(report.iter());
+
+        Ok(report)
+    }
+
+    /// Consumes the next report and appends its events to `out`.
+    ///
+    /// Unlike [`Impl::next_report`], this does not hand out a reference to the shared queue, so
+    /// it never causes the queue to be cloned.
+    fn next_report_into(
+        &mut self,
+        iface: &mut impl Interface,
+        out: &mut Vec<InputEvent>,
+    ) -> io::Result<()> {
+        let len = self.next_report_len(iface)?;
+        let events = self.incoming.range(..len);
+        out.extend(events.clone());
+        events.for_each(|ev| self.state.update_state(*ev));
+        self.skip = len;
+        Ok(())
+    }
+
+    /// Stores a copy of a consumed report in the history, if enabled.
+    ///
+    /// The copy gets its own queue, so that the history doesn't keep the (larger) shared queue
+    /// alive, and doesn't force `refill` to clone it.
+    fn record(&mut self, events: impl Iterator<Item = InputEvent>) {
+        if self.history_len == 0 {
+            return;
+        }
+        let queue: VecDeque<_> = events.collect();
+        if self.history.len() == self.history_len {
+            self.history.pop_front();
+        }
+        self.history.push_back(Report {
+            range: 0..=queue.len() - 1,
+            queue: Arc::new(queue),
+        });
+    }
+
+    fn set_history_len(&mut self, len: usize) {
+        self.history_len = len;
+        let excess = self.history.len().saturating_sub(len);
+        self.history.drain(..excess);
+    }
+
+    /// Returns the next report without consuming it.
+    ///
+    /// The device state is only updated if `refill` has to resync after a `SYN_DROPPED`.
+    fn peek_report(&mut self, iface: &mut impl Interface) -> io::Result<Report> {
         let end: usize = match self
             .incoming
             .iter()
@@ -577,18 +742,13 @@ impl Impl {
             .position(report_or_dropped)
         {
             Some(i) => i,
+            // `refill` resets `skip` to 0.
             None => self.refill(iface)?,
         };
 
-        self.incoming
-            .range(self.skip..=self.skip + end)
-            .for_each(|ev| self.state.update_state(*ev));
-        let skip = self.skip;
-        self.skip += end + 1;
-
         Ok(Report {
             queue: self.incoming.clone(),
-            range: skip..=skip + end,
+            range: self.skip..=self.skip + end,
         })
     }
 
@@ -600,10 +760,14 @@ impl Impl {
         self.skip();
 
         let idx = match self.incoming.iter().position(report_or_dropped) {
-            Some(i) => Ok(i),
-            None => self.refill(iface),
+            Some(i) => i,
+            None => self.refill(iface)?,
         };
-        idx.map(|i| i + 1)
+        if self.history_len != 0 {
+            let incoming = self.incoming.clone();
+            self.record(incoming.range(..=idx).copied());
+        }
+        Ok(idx + 1)
     }
     fn next_event(&mut self) -> InputEvent {
         self.skip();
@@ -632,6 +796,32 @@ impl Impl {
         let incoming = Arc::make_mut(&mut self.incoming);
 
         loop {
+            // Any events already in the queue belong to an incomplete report. If the device doesn't
+            // complete it in time, we end it ourselves.
+            if let Some(timeout) = self.syn_timeout {
+                if !incoming.is_empty() && !self.discard_events {
+                    let timed_out = if i.is_nonblocking()? {
+                        // Non-blocking reads must not wait, so the deadline is checked again on
+                        // later calls instead.
+                        let since = *self.incomplete_since.get_or_insert_with(Instant::now);
+                        since.elapsed() >= timeout
+                    } else {
+                        !i.wait_readable(timeout)?
+                    };
+                    if timed_out {
+                        warn!(
+                            "no SYN_REPORT within {timeout:?} after {} events; synthesizing one",
+                            incoming.len(),
+                        );
+                        let time = incoming.back().unwrap().time();
+                        incoming.push_back(SynEvent::new(Syn::REPORT).with_time(time));
+                        self.state.last_event = time;
+                        self.incomplete_since = None;
+                        return Ok(incoming.len() - 1);
+                    }
+                }
+            }
+
             // `VecDeque` has no `set_len` or `as_mut_ptr`, so we have to add dummy elements to read
             // into, and then remove the ones that weren't overwritten.
             let len_before = incoming.len();
@@ -664,33 +854,21 @@ impl Impl {
                 Some(i) => len_before + i,
                 None => continue, // no SYN_x event, try to read more
             };
+            // `report_or_dropped` guarantees that this is either a `SYN_REPORT` or a `SYN_DROPPED`.
             let ev = incoming[end];
-            let syn = match ev.kind() {
-                EventKind::Syn(ev) => ev,
-                _ => unreachable!("got invalid event at the end of a batch: {ev:?}"),
-            };
+            self.incomplete_since = None;
 
             // Save the timestamp of the last event in the batch.
             self.state.last_event = ev.time();
 
-            match syn.syn() {
-                Syn::REPORT => {
-                    if self.discard_events {
-                        // We have to drop this batch.
-                        self.discard_events = false;
-                        drop(incoming.drain(..=end));
-                        continue;
-                    } else {
-                        // We can return this batch.
-                        return Ok(end);
-                    }
-                }
+            match Syn::from_raw(ev.raw_code()) {
                 Syn::DROPPED => {
                     // At least one event has been lost, so we have to resynchronize.
                     // According to the `libevdev` documentation, we we have to:
                     // - Drop all uncommitted events (events that weren't followed up by a `SYN_REPORT`).
                     // - Drop all *future* events until we get a `SYN_REPORT`.
                     warn!("SYN_DROPPED: input events were lost! resyncing");
+                    self.overflow_count += 1;
                     self.discard_events = true;
                     incoming.clear();
 
@@ -705,7 +883,18 @@ impl Impl {
                     // We will return to normal operation once the synthetic events have been
                     // cleared out and all events until the next `SYN_REPORT` have been discarded.
                 }
-                _ => unreachable!("unexpected SYN event at the end of a batch: {syn:?}"),
+                _ => {
+                    if self.discard_events {
+                        // We have to drop this batch.
+                        self.discard_events = false;
+                        drop(incoming.drain(..=end));
+                        continue;
+                    } else {
+                        // We can return this batch. This may be an empty report consisting of
+                        // only the `SYN_REPORT`, which is passed on unchanged.
+                        return Ok(end);
+                    }
+                }
             }
         }
     }
@@ -714,6 +903,9 @@ impl Impl {
 trait Interface {
     fn read(&mut self, dest: &mut [InputEvent]) -> io::Result<usize>;
     fn resync(&self, state: &mut DeviceState, queue: &mut VecDeque<InputEvent>) -> io::Result<()>;
+    /// Waits up to `timeout` for more events, and returns whether any are available.
+    fn wait_readable(&self, timeout: Duration) -> io::Result<bool>;
+    fn is_nonblocking(&self) -> io::Result<bool>;
 }
 
 impl Interface for Evdev {
@@ -721,26 +913,16 @@ impl Interface for Evdev {
         read_raw(&self.file, dest)
     }
 
-    fn resync(&self, state: &mut DeviceState, queue: &mut VecDeque<InputEvent>) -> io::Result<()> {
-        let now = Instant::now();
-        let _d = on_drop(|| debug!("`EventReader::resync` took {:?}", now.elapsed()));
-
-        // Clear out all events, and drain the kernel buffer too, like libevdev does.
-        let mut reads = 0;
+    fn wait_readable(&self, timeout: Duration) -> io::Result<bool> {
+        util::wait_readable(self.as_raw_fd(), timeout)
+    }
 
-        const READ_LIMIT: usize = 16;
-        const READ_SIZE: usize = 128;
-        while self.is_readable()? && reads < READ_LIMIT {
-            let mut out = [InputEvent::zeroed(); READ_SIZE];
-            read_raw(&self.file, &mut out)?;
-            reads += 1;
-        }
-        if reads >= READ_LIMIT {
-            warn!("resync: kernel buffer not empty after {reads}x{READ_SIZE} reads");
-        }
+    fn is_nonblocking(&self) -> io::Result<bool> {
+        Evdev::is_nonblocking(self)
+    }
 
-        state.resync_from(&DeviceState::current(self)?, queue);
-        Ok(())
+    fn resync(&self, state: &mut DeviceState, queue: &mut VecDeque<InputEvent>) -> io::Result<()> {
+        state.resync(self, queue)
     }
 }
 
@@ -794,6 +976,13 @@ impl IntoRawFd for EventReader {
 
 impl EventReader {
     pub(crate) fn new(evdev: Evdev) -> io::Result<Self> {
+        if !evdev.can_read()? {
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                "cannot create an `EventReader` for a device that was opened in write-only mode",
+            ));
+        }
+
         let abs_axes = evdev.supported_abs_axes()?;
 
         let mut this = Self {
@@ -843,7 +1032,7 @@ impl EventReader {
         // reading 512 *reports* should be plenty to exhaust it.
         const MAX_REPORTS: usize = 512;
 
-        let now = Instant::now();
+        let _span = device_span!("EventReader::update", &self.evdev);
 
         let was_nonblocking = self.evdev.set_nonblocking(true)?;
 
@@ -860,10 +1049,7 @@ impl EventReader {
                 }
             }
         }
-        trace!(
-            "`EventReader::update` processed {count} reports in {:?}",
-            now.elapsed()
-        );
+        trace!("`EventReader::update` processed {count} reports");
 
         let res = if !was_nonblocking {
             self.evdev.set_nonblocking(false).map(drop)
@@ -877,6 +1063,43 @@ impl EventReader {
         }
     }
 
+    /// Grabs the device and discards all events that were buffered before the grab.
+    ///
+    /// This first calls [`Evdev::grab`], and then drains all events queued up in the
+    /// [`EventReader`] and in the kernel, fetches the current device state, and synthesizes
+    /// events to bring the [`EventReader`] up to date (just like it does when it is created).
+    /// Grabbing *before* flushing the buffers ensures that no events can slip past in between, so
+    /// every event read afterwards was delivered while the device was grabbed.
+    ///
+    /// The device will be ungrabbed when the returned [`GrabGuard`] is dropped.
+    /// The guard dereferences to the [`EventReader`], so it can be used to read events.
+    ///
+    /// # Errors
+    ///
+    /// This will return an error of type [`io::ErrorKind::ResourceBusy`] if the device is already
+    /// grabbed.
+    /// If resynchronizing the device state fails, the device is ungrabbed again before the error
+    /// is returned.
+    pub fn grab_and_reset(&mut self) -> io::Result<GrabGuard<'_>> {
+        self.evdev.grab()?;
+        let guard = GrabGuard { reader: self };
+        guard.reader.imp.reset(&guard.reader.evdev)?;
+        Ok(guard)
+    }
+
+    /// Discards the cached multitouch slot state and fetches it from the device again.
+    ///
+    /// This can be used to recover when the multitouch state might be stale, for example after
+    /// the device was grabbed by another application for a while.
+    ///
+    /// Like a `SYN_DROPPED` event, this performs a full resynchronization: all events buffered in
+    /// the [`EventReader`] and in the kernel are discarded, the current device state (including
+    /// all multitouch slots) is fetched, and synthetic events are queued that bring the consumer
+    /// up to date.
+    pub fn invalidate_slots(&mut self) -> io::Result<()> {
+        self.imp.reset(&self.evdev)
+    }
+
     /// Returns a [`BitSet`] of all [`Key`]s that are currently pressed.
     #[inline]
     pub fn key_state(&self) -> &BitSet<Key> {
@@ -905,6 +1128,7 @@ impl EventReader {
     ///
     /// `abs` must be less than [`Abs::MT_SLOT`], or this method will panic. To access
     /// multitouch slots, use [`EventReader::slot_state`] instead.
+    /// [`EventReader::try_abs_state`] can be used when `abs` isn't known to be a non-MT axis.
     ///
     /// Call [`EventReader::update`], or drain incoming events using the iterator interface in order
     /// to update the multitouch slot state.
@@ -913,6 +1137,32 @@ impl EventReader {
         self.imp.abs_state(abs)
     }
 
+    /// Returns the current value of any absolute axis, without panicking.
+    ///
+    /// For axes below [`Abs::MT_SLOT`], this returns the same value as [`EventReader::abs_state`].
+    /// For [`Abs::MT_SLOT`] itself, the index of the [`EventReader::current_slot`] is returned,
+    /// and the other multitouch axes return their value in that slot.
+    ///
+    /// Returns [`None`] if `abs` is a multitouch axis that the device doesn't support.
+    pub fn try_abs_state(&self, abs: Abs) -> Option<i32> {
+        self.imp.try_abs_state(abs)
+    }
+
+    /// Returns the range of values of an absolute axis that has been observed in the event stream.
+    ///
+    /// Devices sometimes report a smaller or larger range in their [`AbsInfo`] than they actually
+    /// use, so this can be used to calibrate an axis by moving it to its extremes.
+    ///
+    /// Returns [`None`] if no event for `abs` has been read from this [`EventReader`] yet.
+    ///
+    /// `abs` must be less than [`Abs::MT_SLOT`], or this method will panic.
+    ///
+    /// [`AbsInfo`]: crate::AbsInfo
+    pub fn observed_range(&self, abs: Abs) -> Option<RangeInclusive<i32>> {
+        let (min, max) = self.imp.state.abs_observed[abs.raw() as usize]?;
+        Some(min..=max)
+    }
+
     /// Returns an iterator that yields all multitouch [`Slot`]s that have valid data in them.
     ///
     /// A [`Slot`] is considered valid if its current value of [`Abs::MT_TRACKING_ID`] is
@@ -949,6 +1199,83 @@ impl EventReader {
         self.imp.current_slot()
     }
 
+    /// Returns a [`SlotSnapshot`] containing a copy of the current multitouch state.
+    ///
+    /// This allocates, so it should not be called for every event.
+    pub fn slot_snapshot(&self) -> SlotSnapshot {
+        SlotSnapshot {
+            mt_storage: self.imp.state.mt_storage.clone(),
+        }
+    }
+
+    /// Returns an [`InputState`] containing a copy of the entire device state.
+    ///
+    /// This includes the key, LED, sound, and switch states, the values of all absolute axes, and
+    /// the multitouch state.
+    ///
+    /// Like [`EventReader::slot_snapshot`], this allocates, so it should not be called for every
+    /// event.
+    pub fn export_state(&self) -> InputState {
+        InputState::new(&self.imp.state)
+    }
+
+    /// Returns the number of times events have been lost due to a buffer overflow.
+    ///
+    /// When the kernel's event buffer overflows, it emits a `SYN_DROPPED` event, and the
+    /// [`EventReader`] will transparently resynchronize its state with the device.
+    /// Applications that need to know that input was lost (for example, to invalidate a timing
+    /// window) can compare this value to a previously observed one.
+    #[inline]
+    pub fn overflow_count(&self) -> u64 {
+        self.imp.overflow_count
+    }
+
+    /// Makes the [`EventReader`] retain copies of the last `len` [`Report`]s it has yielded.
+    ///
+    /// The retained reports can be accessed via [`EventReader::recent_reports`].
+    /// This is useful for gesture recognition and other tasks that need a short history of the
+    /// input.
+    ///
+    /// Reports are recorded both when using [`EventReader::reports`] and when using
+    /// [`EventReader::events`] (in which case a report is recorded as soon as its first event is
+    /// yielded).
+    /// A `len` of 0 (the default) disables the history.
+    pub fn with_history(mut self, len: usize) -> Self {
+        self.imp.set_history_len(len);
+        self
+    }
+
+    /// Makes the [`EventReader`] synthesize a `SYN_REPORT` when a device sends events without one.
+    ///
+    /// Some misbehaving (often virtual) devices emit events but never end the report with a
+    /// `SYN_REPORT`, which would make the [`EventReader`] wait forever for the report to complete.
+    /// With a timeout configured, if no further events arrive within `timeout` of an incomplete
+    /// report, the [`EventReader`] appends a synthetic `SYN_REPORT` (with the timestamp of the last
+    /// event) and yields the report, logging a warning.
+    ///
+    /// **Note**: This deviates from the report semantics of the evdev protocol, since a device
+    /// might still send more events that belong to the same report.
+    /// It should only be enabled for devices that are known to be problematic.
+    ///
+    /// In blocking mode, reading blocks for up to `timeout` while an incomplete report is pending.
+    /// In non-blocking mode, reading never waits: it returns [`io::ErrorKind::WouldBlock`] as
+    /// usual, and the `SYN_REPORT` is synthesized by the first read that happens after `timeout`
+    /// has passed since the incomplete report was first seen.
+    /// Nothing wakes up the caller when that happens, so an event loop waiting for the device to
+    /// become readable will only see the report once it polls the [`EventReader`] again.
+    pub fn with_syn_timeout(mut self, timeout: Duration) -> Self {
+        self.imp.syn_timeout = Some(timeout);
+        self
+    }
+
+    /// Returns an iterator over the most recently yielded [`Report`]s, oldest first.
+    ///
+    /// The history has to be enabled with [`EventReader::with_history`], otherwise this iterator
+    /// will be empty.
+    pub fn recent_reports(&self) -> impl DoubleEndedIterator<Item = &Report> + ExactSizeIterator {
+        self.imp.history.iter()
+    }
+
     /// Returns an iterator over incoming events.
     ///
     /// Events read from the iterator will automatically update the state of the [`EventReader`].
@@ -970,6 +1297,20 @@ impl EventReader {
         }
     }
 
+    /// Returns an iterator over incoming events that skips key autorepeat events.
+    ///
+    /// This works like [`EventReader::events`], but drops all [`KeyEvent`]s with
+    /// [`KeyState::REPEAT`] (see [`KeyEvent::is_repeat`]).
+    /// Games and other applications that react to key presses, rather than to text input,
+    /// typically don't want those.
+    ///
+    /// Autorepeat events don't affect the key state tracked by the [`EventReader`], so the skipped
+    /// events are not missing from [`EventReader::key_state`] either.
+    #[inline]
+    pub fn events_no_repeat(&mut self) -> EventsNoRepeat<'_> {
+        EventsNoRepeat(self.events())
+    }
+
     /// Returns an iterator over incoming device reports.
     ///
     /// [`Report`]s are groups of [`InputEvent`]s that belong together.
@@ -989,6 +1330,132 @@ impl EventReader {
         Reports(self)
     }
 
+    /// Returns an iterator over incoming [`Report`]s that change the state of an axis or key
+    /// selected by `filter`.
+    ///
+    /// Reports that don't contain any events for the watched axes or keys, or that only repeat
+    /// their current values, are consumed and skipped.
+    /// Skipped reports still update the state of the [`EventReader`].
+    ///
+    /// Values are compared to the state before and after each report, so a key that is pressed
+    /// and released within the same report does not count as a change.
+    /// Multitouch axes (`ABS_MT_*`) are tracked per slot, so every event for a watched multitouch
+    /// axis counts as a change.
+    ///
+    /// Blocking behavior is the same as for [`EventReader::reports`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use evdevil::event::{Abs, Key};
+    /// use evdevil::reader::ChangeFilter;
+    /// # let mut reader: evdevil::EventReader = todo!();
+    ///
+    /// let filter = ChangeFilter::new()
+    ///     .with_abs_axes([Abs::X, Abs::Y])
+    ///     .with_keys([Key::BTN_SOUTH, Key::BTN_EAST]);
+    /// for report in reader.reports_changing(filter) {
+    ///     let report = report?;
+    ///     println!("{report:?}");
+    /// }
+    /// # std::io::Result::Ok(())
+    /// ```
+    pub fn reports_changing(&mut self, filter: ChangeFilter) -> ReportsChanging<'_> {
+        ReportsChanging {
+            reader: self,
+            filter,
+        }
+    }
+
+    /// Reads the next report and appends its events to `out`.
+    ///
+    /// Returns `true` if a report was read, and `false` if the device is in non-blocking mode and
+    /// no complete report is available.
+    /// The state of the [`EventReader`] is updated just like with [`EventReader::reports`].
+    ///
+    /// This avoids the per-event overhead of [`EventReader::events`] and the shared queue of
+    /// [`Report`], which makes it suitable for high-rate processing with a reused buffer:
+    ///
+    /// ```no_run
+    /// # let mut reader: evdevil::EventReader = todo!();
+    /// let mut batch = Vec::new();
+    /// loop {
+    ///     batch.clear();
+    ///     reader.next_report_into(&mut batch)?;
+    ///     for event in &batch {
+    ///         println!("{event:?}");
+    ///     }
+    /// #   break;
+    /// }
+    /// # std::io::Result::Ok(())
+    /// ```
+    pub fn next_report_into(&mut self, out: &mut Vec<InputEvent>) -> io::Result<bool> {
+        match self.imp.next_report_into(&mut self.evdev, out) {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Returns the next [`Report`] without consuming it.
+    ///
+    /// The returned [`Report`] will be yielded again by the next call to [`Reports::next`] (or its
+    /// events by [`Events::next`]).
+    /// The events of the peeked report only update the state of the [`EventReader`] once the
+    /// report is actually consumed.
+    ///
+    /// If no complete report is buffered in the [`EventReader`], this will read more events from
+    /// the device.
+    /// If that read encounters a `SYN_DROPPED` event, the [`EventReader`] resynchronizes with the
+    /// device before returning, just like when reading reports normally.
+    /// This *does* update the device state, and replaces the buffered events with synthesized
+    /// events that bring the reader up to date (if there are any, they make up the peeked report).
+    /// If the device is in non-blocking mode and no report is available, this returns
+    /// `Ok(None)`; otherwise it will block until a report arrives.
+    pub fn peek_report(&mut self) -> io::Result<Option<Report>> {
+        match self.imp.peek_report(&mut self.evdev) {
+            Ok(report) => Ok(Some(report)),
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Attempts to fetch the next [`Report`], for use with custom executors.
+    ///
+    /// This is a low-level building block for integrating [`EventReader`] into an executor that
+    /// isn't supported by the `"tokio"` or `"async-io"` features.
+    /// It does not depend on any async runtime and does *not* register any interest in the file
+    /// descriptor: if no report is available, `Poll::Pending` is returned, and the caller is
+    /// responsible for arranging for the waker in `cx` to be woken once the [`EventReader`]
+    /// becomes readable (for example, by registering its file descriptor with `mio` or `epoll`).
+    ///
+    /// The device has to be put in non-blocking mode (via [`Evdev::set_nonblocking`]) before
+    /// calling this method, or it will block until a report arrives.
+    ///
+    /// This never returns `Poll::Ready(None)`, since the stream of events from a device never
+    /// ends; the [`Option`] mirrors the signature of `Stream::poll_next`.
+    pub fn poll_next_report(&mut self, _cx: &mut Context<'_>) -> Poll<Option<io::Result<Report>>> {
+        match self.reports().next() {
+            Some(res) => Poll::Ready(Some(res)),
+            None => Poll::Pending,
+        }
+    }
+
+    /// Attempts to fetch the next [`InputEvent`], for use with custom executors.
+    ///
+    /// This is the per-event equivalent of [`EventReader::poll_next_report`], and has the same
+    /// requirements: the device has to be in non-blocking mode, and the caller has to wake the
+    /// waker in `cx` when the [`EventReader`] becomes readable.
+    pub fn poll_next_event(
+        &mut self,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Option<io::Result<InputEvent>>> {
+        match self.events().next() {
+            Some(res) => Poll::Ready(Some(res)),
+            None => Poll::Pending,
+        }
+    }
+
     /// Returns an async iterator over incoming events.
     ///
     /// Events read from the iterator will automatically update the state of the [`EventReader`].
@@ -1028,7 +1495,7 @@ impl EventReader {
     }
 }
 
-fn report_or_dropped(ev: &InputEvent) -> bool {
+pub(crate) fn report_or_dropped(ev: &InputEvent) -> bool {
     match ev.kind() {
         EventKind::Syn(ev) => ev.syn() == Syn::REPORT || ev.syn() == Syn::DROPPED,
         _ => false,
@@ -1086,6 +1553,28 @@ impl Iterator for Events<'_> {
         self.remaining -= 1;
         Some(Ok(self.reader.next_event()))
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, None)
+    }
+}
+
+/// An [`Iterator`] over the events produced by an [`EventReader`], without key autorepeat events.
+///
+/// Returned by [`EventReader::events_no_repeat`].
+#[derive(Debug)]
+pub struct EventsNoRepeat<'a>(Events<'a>);
+
+impl Iterator for EventsNoRepeat<'_> {
+    type Item = io::Result<InputEvent>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.find(|res| match res {
+            Ok(ev) => !matches!(ev.kind(), EventKind::Key(ev) if ev.is_repeat()),
+            Err(_) => true,
+        })
+    }
 }
 
 /// An owning [`Iterator`] over the events produced by an [`EventReader`].
@@ -1179,6 +1668,103 @@ impl Report {
     pub fn len(&self) -> usize {
         *self.range.end() - *self.range.start() + 1
     }
+
+    /// Returns an iterator over the [`EventKind`]s of the events in this [`Report`].
+    ///
+    /// This calls [`InputEvent::kind`] on every event, which is convenient for code that `match`es
+    /// on the event kind:
+    ///
+    /// ```no_run
+    /// use evdevil::event::EventKind;
+    /// # let mut reader: evdevil::EventReader = todo!();
+    ///
+    /// for report in reader.reports() {
+    ///     for kind in report?.kinds() {
+    ///         match kind {
+    ///             EventKind::Key(ev) => println!("key {:?} {:?}", ev.key(), ev.state()),
+    ///             EventKind::Rel(ev) => println!("{:?} moved by {}", ev.rel(), ev.value()),
+    ///             _ => {}
+    ///         }
+    ///     }
+    /// }
+    /// # std::io::Result::Ok(())
+    /// ```
+    pub fn kinds(&self) -> impl Iterator<Item = EventKind> + '_ {
+        self.iter().map(|ev| ev.kind())
+    }
+
+    /// Returns an iterator over the events in this [`Report`], with redundant events removed.
+    ///
+    /// For [state-bearing] event types like [`EventType::KEY`] and [`EventType::ABS`], only the
+    /// last event for each event code is yielded, since it determines the new state at the end of
+    /// the report.
+    /// All other events (including [`EventType::REL`] events, which have to be summed up instead)
+    /// are yielded unchanged, and the relative order of all yielded events is preserved.
+    ///
+    /// Multitouch events are coalesced per slot: `ABS_MT_*` events are only considered redundant
+    /// if they are followed by another event with the same code *before* the next
+    /// [`Abs::MT_SLOT`] event.
+    ///
+    /// **Note**: This only preserves the net effect of the report. For example, if a key is
+    /// pressed and released within the same report, only the release will be yielded.
+    ///
+    /// [state-bearing]: EventType::is_state_bearing
+    pub fn coalesced(&self) -> impl Iterator<Item = InputEvent> + '_ {
+        let events = self.queue.range(self.range.clone());
+        events.clone().enumerate().filter_map(move |(i, ev)| {
+            let ty = ev.event_type();
+            if !ty.is_state_bearing()
+                || (ty == EventType::ABS && ev.raw_code() == Abs::MT_SLOT.raw())
+            {
+                return Some(*ev);
+            }
+
+            let is_mt = ty == EventType::ABS && ev.raw_code() > Abs::MT_SLOT.raw();
+            let superseded = events
+                .clone()
+                .skip(i + 1)
+                .take_while(|next| {
+                    !is_mt
+                        || next.event_type() != EventType::ABS
+                        || next.raw_code() != Abs::MT_SLOT.raw()
+                })
+                .any(|next| next.event_type() == ty && next.raw_code() == ev.raw_code());
+            if superseded { None } else { Some(*ev) }
+        })
+    }
+
+    /// Returns an iterator over the [`KeyEvent`]s in this [`Report`], paired with their
+    /// [`Scancode`].
+    ///
+    /// Devices that support keymaps typically send a [`Misc::SCAN`] event carrying the hardware
+    /// scancode right before the [`KeyEvent`] it belongs to.
+    /// This pairs every [`KeyEvent`] with the [`Misc::SCAN`] event preceding it, or with [`None`]
+    /// if there is no such event (or if it has already been paired with an earlier [`KeyEvent`]).
+    ///
+    /// The [`Scancode`] can be used with [`Evdev::keymap_entry`] and [`Evdev::set_keymap_entry`]
+    /// to remap the key.
+    pub fn keys_with_scancodes(&self) -> impl Iterator<Item = (Option<Scancode>, KeyEvent)> + '_ {
+        let mut scancode = None;
+        self.iter().filter_map(move |ev| match ev.kind() {
+            EventKind::Misc(ev) if ev.misc() == Misc::SCAN => {
+                scancode = Some(Scancode::from(ev.raw_value() as u32));
+                None
+            }
+            EventKind::Key(ev) => Some((scancode.take(), ev)),
+            _ => None,
+        })
+    }
+
+    /// Returns the [`Scancode`] that was sent along with the [`KeyEvent`] for `key`.
+    ///
+    /// Returns [`None`] if this [`Report`] doesn't contain a [`KeyEvent`] for `key`, or if that
+    /// event wasn't preceded by a [`Misc::SCAN`] event.
+    /// See [`Report::keys_with_scancodes`] for details.
+    pub fn scan_for_key(&self, key: Key) -> Option<Scancode> {
+        self.keys_with_scancodes()
+            .find(|(_, ev)| ev.key() == key)
+            .and_then(|(scancode, _)| scancode)
+    }
 }
 
 impl<'a> IntoIterator for &'a Report {
@@ -1258,3 +1844,106 @@ impl<'a> Iterator for ReportIter<'a> {
 }
 impl ExactSizeIterator for ReportIter<'_> {}
 impl FusedIterator for ReportIter<'_> {}
+
+/// Keeps an [`EventReader`]'s device grabbed.
+///
+/// Returned by [`EventReader::grab_and_reset`].
+///
+/// Dereferences to the [`EventReader`].
+/// When dropped, the device is ungrabbed again.
+#[derive(Debug)]
+pub struct GrabGuard<'a> {
+    reader: &'a mut EventReader,
+}
+
+impl GrabGuard<'_> {
+    /// Ungrabs the device, returning any error that occurs.
+    ///
+    /// Dropping the [`GrabGuard`] will also ungrab the device, but will only log errors.
+    pub fn ungrab(self) -> io::Result<()> {
+        let this = ManuallyDrop::new(self);
+        this.reader.evdev.ungrab()
+    }
+}
+
+impl Deref for GrabGuard<'_> {
+    type Target = EventReader;
+
+    #[inline]
+    fn deref(&self) -> &EventReader {
+        self.reader
+    }
+}
+
+impl DerefMut for GrabGuard<'_> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut EventReader {
+        self.reader
+    }
+}
+
+impl Drop for GrabGuard<'_> {
+    fn drop(&mut self) {
+        if let Err(e) = self.reader.evdev.ungrab() {
+            error!("failed to ungrab evdev: {e}");
+        }
+    }
+}
+
+/// Detects lost events in a raw event stream.
+///
+/// [`EventReader`] handles `SYN_DROPPED` events automatically, but users of
+/// [`Evdev::raw_events`] and [`Evdev::read_events`] have to do so themselves.
+/// An [`OverflowCounter`] can be fed every raw event read from the device, and will count the
+/// number of `SYN_DROPPED` events it has seen.
+///
+/// After a `SYN_DROPPED` event, all events up to and including the next `SYN_REPORT` belong to an
+/// incomplete report and should be discarded; [`OverflowCounter::observe`] reports this.
+/// Since the application has missed state changes at that point, it should then fetch the current
+/// device state (eg. via [`Evdev::key_state`]) if it tracks any.
+#[derive(Debug, Clone, Default)]
+pub struct OverflowCounter {
+    dropped: u64,
+    discarding: bool,
+}
+
+impl OverflowCounter {
+    /// Creates a new [`OverflowCounter`] that has not seen any `SYN_DROPPED` events.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feeds an event into the [`OverflowCounter`].
+    ///
+    /// Returns `true` if the event should be processed by the application, and `false` if it
+    /// should be discarded because it is a `SYN_DROPPED` event or part of the incomplete report
+    /// following one.
+    pub fn observe(&mut self, event: &InputEvent) -> bool {
+        match event.kind() {
+            EventKind::Syn(ev) if ev.syn() == Syn::DROPPED => {
+                self.dropped += 1;
+                self.discarding = true;
+                false
+            }
+            EventKind::Syn(ev) if ev.syn() == Syn::REPORT && self.discarding => {
+                self.discarding = false;
+                false
+            }
+            _ => !self.discarding,
+        }
+    }
+
+    /// Returns the number of `SYN_DROPPED` events observed so far.
+    #[inline]
+    pub fn count(&self) -> u64 {
+        self.dropped
+    }
+
+    /// Returns whether events are currently being discarded, because a `SYN_DROPPED` event has
+    /// been observed, but the following `SYN_REPORT` has not.
+    #[inline]
+    pub fn is_discarding(&self) -> bool {
+        self.discarding
+    }
+}
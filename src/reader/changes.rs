@@ -0,0 +1,118 @@
+use std::io;
+
+use crate::{
+    bits::BitSet,
+    event::{Abs, EventKind, Key},
+    reader::{EventReader, Impl, Interface, Report},
+};
+
+/// Selects the [`Abs`] axes and [`Key`]s whose changes [`EventReader::reports_changing`] should
+/// report.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChangeFilter {
+    abs: BitSet<Abs>,
+    keys: BitSet<Key>,
+}
+
+impl ChangeFilter {
+    /// Creates an empty [`ChangeFilter`] that doesn't watch anything.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds absolute axes to the set of watched axes.
+    pub fn with_abs_axes(mut self, axes: impl IntoIterator<Item = Abs>) -> Self {
+        self.abs.extend(axes);
+        self
+    }
+
+    /// Adds keys to the set of watched keys.
+    pub fn with_keys(mut self, keys: impl IntoIterator<Item = Key>) -> Self {
+        self.keys.extend(keys);
+        self
+    }
+
+    /// Returns the set of watched absolute axes.
+    #[inline]
+    pub fn abs_axes(&self) -> &BitSet<Abs> {
+        &self.abs
+    }
+
+    /// Returns the set of watched keys.
+    #[inline]
+    pub fn keys(&self) -> &BitSet<Key> {
+        &self.keys
+    }
+}
+
+/// Iterator over the [`Report`]s that change a watched axis or key.
+///
+/// Returned by [`EventReader::reports_changing`].
+///
+/// Like [`Reports`][super::Reports], this will yield [`None`] when the [`EventReader`] is in
+/// non-blocking mode and no events are pending.
+#[derive(Debug)]
+pub struct ReportsChanging<'a> {
+    pub(super) reader: &'a mut EventReader,
+    pub(super) filter: ChangeFilter,
+}
+
+impl ReportsChanging<'_> {
+    /// Returns the [`ChangeFilter`] used by this iterator.
+    #[inline]
+    pub fn filter(&self) -> &ChangeFilter {
+        &self.filter
+    }
+}
+
+impl Iterator for ReportsChanging<'_> {
+    type Item = io::Result<Report>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let reader = &mut *self.reader;
+        match reader
+            .imp
+            .next_changing_report(&mut reader.evdev, &self.filter)
+        {
+            Ok(report) => Some(Ok(report)),
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => None,
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+impl Impl {
+    /// Consumes reports until one changes the state of an axis or key selected by `filter`, and
+    /// returns it.
+    ///
+    /// All consumed reports update the device state, including the skipped ones.
+    pub(super) fn next_changing_report(
+        &mut self,
+        iface: &mut impl Interface,
+        filter: &ChangeFilter,
+    ) -> io::Result<Report> {
+        loop {
+            let keys_before = self.state.keys;
+            let abs_before = self.state.abs;
+            let report = self.next_report(iface)?;
+
+            let changed = report.iter().any(|ev| match ev.kind() {
+                EventKind::Key(ev) if filter.keys.contains(ev.key()) => {
+                    keys_before.contains(ev.key()) != self.state.keys.contains(ev.key())
+                }
+                EventKind::Abs(ev) if filter.abs.contains(ev.abs()) => {
+                    match abs_before.get(usize::from(ev.abs().raw())) {
+                        Some(before) => *before != self.abs_state(ev.abs()),
+                        // Multitouch axes are tracked per slot; every event counts as a change.
+                        None => true,
+                    }
+                }
+                _ => false,
+            });
+            if changed {
+                return Ok(report);
+            }
+        }
+    }
+}
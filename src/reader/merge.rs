@@ -0,0 +1,199 @@
+use std::{io, mem, os::fd::AsRawFd, time::SystemTime};
+
+use crate::reader::{EventReader, Report};
+
+/// Merges the [`Report`]s of several [`EventReader`]s into a single stream.
+///
+/// [`Merge`] implements [`Iterator`] and yields every [`Report`] together with the index of the
+/// [`EventReader`] it came from (its position in the list passed to [`Merge::new`]).
+/// When reports from several devices are available at the same time, they are yielded in order of
+/// their timestamps.
+///
+/// While the [`EventReader`]s are owned by the [`Merge`], their devices are put in non-blocking
+/// mode.
+/// Whether the [`Merge`] itself blocks is controlled by [`Merge::set_nonblocking`]:
+///
+/// - In blocking mode (the default), [`Iterator::next`] will wait until any of the devices has a
+///   report available.
+/// - In non-blocking mode, [`Iterator::next`] will return [`None`] when no device has a report
+///   available (later calls may return [`Some`] again).
+///
+/// Devices that are unplugged (and fail with `ENODEV`) are dropped from the [`Merge`]
+/// automatically.
+/// Once all devices are gone, the iterator will return [`None`].
+#[derive(Debug)]
+pub struct Merge {
+    devices: Vec<Device>,
+    nonblocking: bool,
+}
+
+#[derive(Debug)]
+struct Device {
+    index: usize,
+    reader: EventReader,
+    pending: Option<Report>,
+    was_nonblocking: bool,
+}
+
+impl Device {
+    fn restore_mode(&self) {
+        if self.was_nonblocking {
+            return;
+        }
+
+        if let Err(e) = self.reader.evdev().set_nonblocking(false) {
+            error!("failed to move evdev back into blocking mode: {e}");
+        }
+    }
+}
+
+impl Merge {
+    /// Creates a [`Merge`] that yields the reports of all `readers`.
+    pub fn new(readers: impl IntoIterator<Item = EventReader>) -> io::Result<Self> {
+        let mut this = Self {
+            devices: Vec::new(),
+            nonblocking: false,
+        };
+        for (index, reader) in readers.into_iter().enumerate() {
+            let was_nonblocking = reader.evdev().set_nonblocking(true)?;
+            this.devices.push(Device {
+                index,
+                reader,
+                pending: None,
+                was_nonblocking,
+            });
+        }
+        Ok(this)
+    }
+
+    /// Controls whether iterating over the [`Merge`] blocks until a report is available.
+    ///
+    /// Returns whether the [`Merge`] was previously in non-blocking mode.
+    pub fn set_nonblocking(&mut self, nonblocking: bool) -> bool {
+        mem::replace(&mut self.nonblocking, nonblocking)
+    }
+
+    /// Returns the number of devices that are still part of the [`Merge`].
+    pub fn len(&self) -> usize {
+        self.devices.len()
+    }
+
+    /// Returns whether all devices have been removed from the [`Merge`].
+    pub fn is_empty(&self) -> bool {
+        self.devices.is_empty()
+    }
+
+    /// Returns the [`EventReader`] with the given `index`.
+    ///
+    /// Returns [`None`] if `index` is out of range, or if the device has been removed from the
+    /// [`Merge`] because it was unplugged.
+    pub fn reader(&self, index: usize) -> Option<&EventReader> {
+        self.devices
+            .iter()
+            .find(|dev| dev.index == index)
+            .map(|dev| &dev.reader)
+    }
+
+    /// Returns the remaining [`EventReader`]s, along with their indices.
+    ///
+    /// The devices are moved back into the blocking mode they were in when the [`Merge`] was
+    /// created.
+    /// Any [`Report`]s that have been read from the devices, but not yet yielded by the [`Merge`],
+    /// are lost.
+    pub fn into_readers(mut self) -> Vec<(usize, EventReader)> {
+        mem::take(&mut self.devices)
+            .into_iter()
+            .map(|dev| {
+                dev.restore_mode();
+                (dev.index, dev.reader)
+            })
+            .collect()
+    }
+
+    /// Reads a [`Report`] from every device that doesn't already have one pending.
+    fn fill(&mut self) -> io::Result<()> {
+        let mut i = 0;
+        while i < self.devices.len() {
+            let dev = &mut self.devices[i];
+            if dev.pending.is_none() {
+                match dev.reader.reports().next() {
+                    Some(Ok(report)) => dev.pending = Some(report),
+                    Some(Err(e)) if e.raw_os_error() == Some(libc::ENODEV) => {
+                        debug!("device {} was removed, dropping it from `Merge`", dev.index);
+                        self.devices.remove(i);
+                        continue;
+                    }
+                    Some(Err(e)) => return Err(e),
+                    None => {}
+                }
+            }
+            i += 1;
+        }
+        Ok(())
+    }
+
+    /// Blocks until any of the devices becomes readable.
+    fn wait(&self) -> io::Result<()> {
+        let mut fds = self
+            .devices
+            .iter()
+            .map(|dev| libc::pollfd {
+                fd: dev.reader.as_raw_fd(),
+                events: libc::POLLIN,
+                revents: 0,
+            })
+            .collect::<Vec<_>>();
+        let ret = unsafe { libc::poll(fds.as_mut_ptr(), fds.len() as libc::nfds_t, -1) };
+        if ret == -1 {
+            let err = io::Error::last_os_error();
+            if err.kind() != io::ErrorKind::Interrupted {
+                return Err(err);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Iterator for Merge {
+    type Item = io::Result<(usize, Report)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Err(e) = self.fill() {
+                return Some(Err(e));
+            }
+
+            let earliest = self
+                .devices
+                .iter_mut()
+                .filter(|dev| dev.pending.is_some())
+                .min_by_key(|dev| dev.pending.as_ref().map(report_time));
+            if let Some(dev) = earliest {
+                return Some(Ok((dev.index, dev.pending.take().unwrap())));
+            }
+
+            if self.nonblocking || self.devices.is_empty() {
+                return None;
+            }
+            if let Err(e) = self.wait() {
+                return Some(Err(e));
+            }
+        }
+    }
+}
+
+impl Drop for Merge {
+    fn drop(&mut self) {
+        for dev in &self.devices {
+            dev.restore_mode();
+        }
+    }
+}
+
+/// Returns the timestamp of the `SYN_REPORT` event that ends `report`.
+fn report_time(report: &Report) -> SystemTime {
+    report
+        .iter()
+        .last()
+        .map_or(SystemTime::UNIX_EPOCH, |ev| ev.time())
+}
@@ -0,0 +1,90 @@
+use crate::{
+    bits::BitSet,
+    event::{Abs, Key, Led, Sound, Switch},
+    reader::{DeviceState, SlotSnapshot},
+};
+
+/// An owned copy of the entire device state tracked by an [`EventReader`].
+///
+/// Returned by [`EventReader::export_state`].
+///
+/// This is meant for applications that want to look at the complete input state at specific
+/// points in time (for example, once per frame) instead of processing the event stream.
+/// Since [`InputState`] does not borrow from the [`EventReader`], it can be sent to another thread.
+///
+/// [`EventReader`]: super::EventReader
+/// [`EventReader::export_state`]: super::EventReader::export_state
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputState {
+    keys: BitSet<Key>,
+    leds: BitSet<Led>,
+    sounds: BitSet<Sound>,
+    switches: BitSet<Switch>,
+    abs_axes: BitSet<Abs>,
+    abs: [i32; Abs::MT_SLOT.raw() as usize],
+    slots: SlotSnapshot,
+}
+
+impl InputState {
+    pub(super) fn new(state: &DeviceState) -> Self {
+        Self {
+            keys: state.keys,
+            leds: state.leds,
+            sounds: state.sounds,
+            switches: state.switches,
+            abs_axes: state.abs_axes,
+            abs: state.abs,
+            slots: SlotSnapshot {
+                mt_storage: state.mt_storage.clone(),
+            },
+        }
+    }
+
+    /// Returns the set of pressed keys.
+    #[inline]
+    pub fn keys(&self) -> &BitSet<Key> {
+        &self.keys
+    }
+
+    /// Returns the set of LEDs that are on.
+    #[inline]
+    pub fn leds(&self) -> &BitSet<Led> {
+        &self.leds
+    }
+
+    /// Returns the set of sounds that are playing.
+    #[inline]
+    pub fn sounds(&self) -> &BitSet<Sound> {
+        &self.sounds
+    }
+
+    /// Returns the set of switches that are on.
+    #[inline]
+    pub fn switches(&self) -> &BitSet<Switch> {
+        &self.switches
+    }
+
+    /// Returns the value of the absolute axis `abs`.
+    ///
+    /// Returns [`None`] if the device does not support `abs`, or if `abs` is a multitouch axis
+    /// (those can be queried via [`InputState::slots`]).
+    pub fn abs(&self, abs: Abs) -> Option<i32> {
+        if !self.abs_axes.contains(abs) {
+            return None;
+        }
+        self.abs.get(usize::from(abs.raw())).copied()
+    }
+
+    /// Returns an iterator over all supported non-multitouch absolute axes and their values.
+    pub fn abs_values(&self) -> impl Iterator<Item = (Abs, i32)> + '_ {
+        self.abs_axes
+            .iter()
+            .filter_map(|abs| Some((abs, self.abs(abs)?)))
+    }
+
+    /// Returns the multitouch state.
+    #[inline]
+    pub fn slots(&self) -> &SlotSnapshot {
+        &self.slots
+    }
+}
@@ -1,7 +1,7 @@
 use std::{cmp::min, time::Duration};
 
 use crate::{
-    event::{Rel, RelEvent},
+    event::{MiscEvent, Rel, RelEvent},
     test::check_events,
 };
 
@@ -9,10 +9,16 @@ use super::*;
 
 struct TestIntf {
     raw_events: Vec<InputEvent>,
+    /// Returned by `is_nonblocking`.
+    nonblocking: bool,
 }
 
 impl Interface for TestIntf {
     fn read(&mut self, dest: &mut [InputEvent]) -> io::Result<usize> {
+        if self.raw_events.is_empty() {
+            // Behave like a device in non-blocking mode.
+            return Err(io::ErrorKind::WouldBlock.into());
+        }
         let n = min(dest.len(), self.raw_events.len());
         dest[..n].copy_from_slice(&self.raw_events[..n]);
         self.raw_events.drain(..n);
@@ -24,7 +30,20 @@ impl Interface for TestIntf {
         _state: &mut DeviceState,
         _queue: &mut VecDeque<InputEvent>,
     ) -> io::Result<()> {
-        unimplemented!()
+        // Pretend that the device state hasn't changed, so no events need to be synthesized.
+        Ok(())
+    }
+
+    fn wait_readable(&self, _timeout: Duration) -> io::Result<bool> {
+        assert!(
+            !self.nonblocking,
+            "`wait_readable` called in non-blocking mode"
+        );
+        Ok(!self.raw_events.is_empty())
+    }
+
+    fn is_nonblocking(&self) -> io::Result<bool> {
+        Ok(self.nonblocking)
     }
 }
 
@@ -39,6 +58,7 @@ impl EventReaderTest {
             imp: Impl::new(BitSet::new()),
             test: TestIntf {
                 raw_events: Vec::new(),
+                nonblocking: false,
             },
         }
     }
@@ -113,6 +133,158 @@ fn shared_reports() -> io::Result<()> {
     Ok(())
 }
 
+#[test]
+fn observed_range() -> io::Result<()> {
+    let mut reader = EventReaderTest::new();
+    assert_eq!(reader.imp.state.abs_observed[Abs::X.raw() as usize], None);
+
+    reader.append_events([AbsEvent::new(Abs::X, 10).into(), Syn::REPORT.into()]);
+    reader.append_events([AbsEvent::new(Abs::X, -5).into(), Syn::REPORT.into()]);
+    reader.append_events([AbsEvent::new(Abs::X, 3).into(), Syn::REPORT.into()]);
+    for _ in 0..3 {
+        reader.next_report()?;
+    }
+    assert_eq!(
+        reader.imp.state.abs_observed[Abs::X.raw() as usize],
+        Some((-5, 10))
+    );
+    assert_eq!(reader.imp.abs_state(Abs::X), 3);
+    assert_eq!(reader.imp.state.abs_observed[Abs::Y.raw() as usize], None);
+
+    Ok(())
+}
+
+#[test]
+fn try_abs_state() {
+    let mut reader = EventReaderTest::new();
+    reader.imp.state.abs[Abs::X.raw() as usize] = 5;
+    assert_eq!(reader.imp.try_abs_state(Abs::X), Some(5));
+    assert_eq!(reader.imp.try_abs_state(Abs::MT_SLOT), None);
+    assert_eq!(reader.imp.try_abs_state(Abs::MT_POSITION_X), None);
+
+    reader.imp.state.abs_axes.insert(Abs::MT_SLOT);
+    reader.imp.state.mt_storage = MtStorage::new_test(2, &[Abs::MT_POSITION_X])
+        .with_value(1, Abs::MT_POSITION_X, 100)
+        .with_active_slot(1);
+    assert_eq!(reader.imp.try_abs_state(Abs::MT_SLOT), Some(1));
+    assert_eq!(reader.imp.try_abs_state(Abs::MT_POSITION_X), Some(100));
+    assert_eq!(reader.imp.try_abs_state(Abs::MT_POSITION_Y), None);
+}
+
+#[test]
+fn history() -> io::Result<()> {
+    let mut reader = EventReaderTest::new();
+    reader.imp.set_history_len(2);
+    for i in 1..=3 {
+        reader.append_events([RelEvent::new(Rel::DIAL, i).into(), Syn::REPORT.into()]);
+        reader.next_report()?;
+    }
+    assert_eq!(reader.imp.history.len(), 2);
+    check_events(
+        &reader.imp.history[0],
+        [RelEvent::new(Rel::DIAL, 2).into(), Syn::REPORT.into()],
+    );
+    check_events(
+        &reader.imp.history[1],
+        [RelEvent::new(Rel::DIAL, 3).into(), Syn::REPORT.into()],
+    );
+
+    // Reports consumed event-by-event are recorded too.
+    reader.append_events([RelEvent::new(Rel::DIAL, 4).into(), Syn::REPORT.into()]);
+    assert_eq!(reader.imp.next_report_len(&mut reader.test)?, 2);
+    check_events(
+        &reader.imp.history[1],
+        [RelEvent::new(Rel::DIAL, 4).into(), Syn::REPORT.into()],
+    );
+
+    reader.imp.set_history_len(1);
+    assert_eq!(reader.imp.history.len(), 1);
+    reader.imp.set_history_len(0);
+    assert!(reader.imp.history.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn overflow_count() -> io::Result<()> {
+    let mut reader = EventReaderTest::new();
+    reader.append_events([RelEvent::new(Rel::DIAL, 0).into(), Syn::DROPPED.into()]);
+    let err = reader.next_report().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::WouldBlock);
+    assert_eq!(reader.imp.overflow_count, 1);
+
+    // Events up to the next `SYN_REPORT` are discarded.
+    reader.append_events([RelEvent::new(Rel::DIAL, 1).into(), Syn::REPORT.into()]);
+    let err = reader.next_report().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::WouldBlock);
+
+    reader.append_events([RelEvent::new(Rel::DIAL, 2).into(), Syn::REPORT.into()]);
+    let report = reader.next_report()?;
+    check_events(
+        report,
+        [RelEvent::new(Rel::DIAL, 2).into(), Syn::REPORT.into()],
+    );
+    assert_eq!(reader.imp.overflow_count, 1);
+
+    Ok(())
+}
+
+#[test]
+fn overflow_counter() {
+    let mut counter = OverflowCounter::new();
+    assert!(counter.observe(&RelEvent::new(Rel::DIAL, 0).into()));
+    assert!(counter.observe(&Syn::REPORT.into()));
+    assert_eq!(counter.count(), 0);
+
+    assert!(!counter.observe(&Syn::DROPPED.into()));
+    assert!(counter.is_discarding());
+    assert!(!counter.observe(&RelEvent::new(Rel::DIAL, 1).into()));
+    assert!(!counter.observe(&Syn::REPORT.into()));
+    assert!(!counter.is_discarding());
+    assert_eq!(counter.count(), 1);
+
+    assert!(counter.observe(&RelEvent::new(Rel::DIAL, 2).into()));
+    assert!(counter.observe(&Syn::REPORT.into()));
+    assert_eq!(counter.count(), 1);
+}
+
+#[test]
+fn peek_report() -> io::Result<()> {
+    let mut reader = EventReaderTest::new();
+    reader.append_events([RelEvent::new(Rel::DIAL, 0).into(), Syn::REPORT.into()]);
+    reader.append_events([RelEvent::new(Rel::DIAL, 1).into(), Syn::REPORT.into()]);
+
+    let peeked = reader.imp.peek_report(&mut reader.test)?;
+    check_events(
+        &peeked,
+        [RelEvent::new(Rel::DIAL, 0).into(), Syn::REPORT.into()],
+    );
+    let peeked = reader.imp.peek_report(&mut reader.test)?;
+    check_events(
+        &peeked,
+        [RelEvent::new(Rel::DIAL, 0).into(), Syn::REPORT.into()],
+    );
+
+    let report = reader.next_report()?;
+    check_events(
+        report,
+        [RelEvent::new(Rel::DIAL, 0).into(), Syn::REPORT.into()],
+    );
+
+    let peeked = reader.imp.peek_report(&mut reader.test)?;
+    check_events(
+        &peeked,
+        [RelEvent::new(Rel::DIAL, 1).into(), Syn::REPORT.into()],
+    );
+    let report = reader.next_report()?;
+    check_events(
+        report,
+        [RelEvent::new(Rel::DIAL, 1).into(), Syn::REPORT.into()],
+    );
+
+    Ok(())
+}
+
 /// Functionality for multitouch tests below.
 impl MtStorage {
     fn new_test(slots: u32, codes: &[Abs]) -> Self {
@@ -213,3 +385,365 @@ fn mt_resync_slot_data() {
         ],
     );
 }
+
+#[test]
+fn slot_snapshot() {
+    let snapshot = SlotSnapshot {
+        mt_storage: MtStorage::new_test(3, &[Abs::MT_TRACKING_ID, Abs::MT_POSITION_X])
+            .with_value(0, Abs::MT_TRACKING_ID, -1)
+            .with_value(1, Abs::MT_TRACKING_ID, 7)
+            .with_value(1, Abs::MT_POSITION_X, 100)
+            .with_value(2, Abs::MT_TRACKING_ID, 8)
+            .with_active_slot(2),
+    };
+
+    assert_eq!(
+        snapshot.slots().collect::<Vec<_>>(),
+        [Slot::from_raw(1), Slot::from_raw(2)],
+    );
+    assert_eq!(snapshot.get(1, Abs::MT_POSITION_X), Some(100));
+    assert_eq!(snapshot.get(1, Abs::MT_POSITION_Y), None);
+    assert_eq!(snapshot.get(3, Abs::MT_POSITION_X), None);
+    assert_eq!(snapshot.current_slot(), Slot::from_raw(2));
+}
+
+#[test]
+fn empty_reports() -> io::Result<()> {
+    let mut reader = EventReaderTest::new();
+    reader.append_events([
+        Syn::REPORT.into(),
+        Syn::REPORT.into(),
+        RelEvent::new(Rel::DIAL, 1).into(),
+        Syn::REPORT.into(),
+    ]);
+
+    check_events(reader.next_report()?, [Syn::REPORT.into()]);
+    check_events(reader.next_report()?, [Syn::REPORT.into()]);
+    check_events(
+        reader.next_report()?,
+        [RelEvent::new(Rel::DIAL, 1).into(), Syn::REPORT.into()],
+    );
+    assert_eq!(
+        reader.next_report().unwrap_err().kind(),
+        io::ErrorKind::WouldBlock
+    );
+
+    Ok(())
+}
+
+#[test]
+fn malformed_events() -> io::Result<()> {
+    let mut reader = EventReaderTest::new();
+    let events = [
+        // Unusual `SYN` events are passed through as part of the report.
+        Syn::CONFIG.into(),
+        Syn::MT_REPORT.into(),
+        InputEvent::new(EventType::SYN, 0x7, 0),
+        // Out-of-range codes for state-bearing event types are ignored when updating the state.
+        InputEvent::new(EventType::KEY, 0xffff, 1),
+        InputEvent::new(EventType::LED, 0xffff, 1),
+        InputEvent::new(EventType::SW, 0xffff, 1),
+        InputEvent::new(EventType::SND, 0xffff, 1),
+        AbsEvent::new(Abs::MT_SLOT, -1).into(),
+        AbsEvent::new(Abs::MT_POSITION_X, 5).into(),
+        Syn::REPORT.into(),
+    ];
+    reader.append_events(events);
+
+    let report = reader.next_report()?;
+    check_events(report, events);
+    assert!(reader.imp.state.keys.is_empty());
+    assert!(reader.imp.state.leds.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn report_scancodes() -> io::Result<()> {
+    let mut reader = EventReaderTest::new();
+    reader.append_events([
+        MiscEvent::new(Misc::SCAN, 0x1e).into(),
+        KeyEvent::new(Key::KEY_A, KeyState::PRESSED).into(),
+        KeyEvent::new(Key::KEY_B, KeyState::PRESSED).into(),
+        MiscEvent::new(Misc::SCAN, 0x70004).into(),
+        KeyEvent::new(Key::KEY_C, KeyState::PRESSED).into(),
+        Syn::REPORT.into(),
+    ]);
+    let report = reader.next_report()?;
+
+    let pairs = report
+        .keys_with_scancodes()
+        .map(|(scancode, ev)| (scancode.map(|s| s.to_string()), ev.key()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        pairs,
+        [
+            (Some("1e".to_string()), Key::KEY_A),
+            (None, Key::KEY_B),
+            (Some("70004".to_string()), Key::KEY_C),
+        ]
+    );
+
+    assert_eq!(
+        report.scan_for_key(Key::KEY_C).map(|s| s.to_string()),
+        Some("70004".to_string())
+    );
+    assert!(report.scan_for_key(Key::KEY_B).is_none());
+    assert!(report.scan_for_key(Key::KEY_D).is_none());
+
+    Ok(())
+}
+
+#[test]
+fn report_coalesced() -> io::Result<()> {
+    let mut reader = EventReaderTest::new();
+    reader.append_events([
+        AbsEvent::new(Abs::X, 1).into(),
+        RelEvent::new(Rel::DIAL, 1).into(),
+        AbsEvent::new(Abs::X, 2).into(),
+        KeyEvent::new(Key::KEY_A, KeyState::PRESSED).into(),
+        RelEvent::new(Rel::DIAL, 1).into(),
+        KeyEvent::new(Key::KEY_A, KeyState::RELEASED).into(),
+        AbsEvent::new(Abs::MT_SLOT, 0).into(),
+        AbsEvent::new(Abs::MT_POSITION_X, 10).into(),
+        AbsEvent::new(Abs::MT_POSITION_X, 11).into(),
+        AbsEvent::new(Abs::MT_SLOT, 1).into(),
+        AbsEvent::new(Abs::MT_POSITION_X, 20).into(),
+        AbsEvent::new(Abs::X, 3).into(),
+        Syn::REPORT.into(),
+    ]);
+    let report = reader.next_report()?;
+    assert_eq!(report.len(), 13);
+    check_events(
+        report.coalesced(),
+        [
+            RelEvent::new(Rel::DIAL, 1).into(),
+            RelEvent::new(Rel::DIAL, 1).into(),
+            KeyEvent::new(Key::KEY_A, KeyState::RELEASED).into(),
+            AbsEvent::new(Abs::MT_SLOT, 0).into(),
+            AbsEvent::new(Abs::MT_POSITION_X, 11).into(),
+            AbsEvent::new(Abs::MT_SLOT, 1).into(),
+            AbsEvent::new(Abs::MT_POSITION_X, 20).into(),
+            AbsEvent::new(Abs::X, 3).into(),
+            Syn::REPORT.into(),
+        ],
+    );
+
+    Ok(())
+}
+
+#[test]
+fn non_mt_device() {
+    // Devices without `ABS_MT_SLOT` skip all multitouch handling.
+    let mut state = DeviceState::new(BitSet::new());
+    assert!(!state.has_mt);
+    state.update_state(AbsEvent::new(Abs::MT_SLOT, 1).into());
+    state.update_state(AbsEvent::new(Abs::MT_POSITION_X, 50).into());
+    assert_eq!(state.mt_storage, MtStorage::empty());
+
+    let mut state = DeviceState::new(BitSet::from_iter([Abs::MT_SLOT, Abs::MT_POSITION_X]));
+    assert!(state.has_mt);
+    state.mt_storage = MtStorage::new_test(2, &[Abs::MT_POSITION_X]);
+    state.update_state(AbsEvent::new(Abs::MT_SLOT, 1).into());
+    state.update_state(AbsEvent::new(Abs::MT_POSITION_X, 50).into());
+    assert_eq!(state.mt_storage.active_slot, 1);
+    assert_eq!(state.mt_storage.slot_state(1, Abs::MT_POSITION_X), Some(50));
+}
+
+#[test]
+fn reports_changing() -> io::Result<()> {
+    let filter = ChangeFilter::new()
+        .with_abs_axes([Abs::X])
+        .with_keys([Key::BTN_SOUTH]);
+    let mut reader = EventReaderTest::new();
+    // Unwatched changes are skipped.
+    reader.append_events([AbsEvent::new(Abs::Y, 1).into(), Syn::REPORT.into()]);
+    reader.append_events([
+        KeyEvent::new(Key::BTN_EAST, KeyState::PRESSED).into(),
+        Syn::REPORT.into(),
+    ]);
+    // Re-reported values are skipped.
+    reader.append_events([AbsEvent::new(Abs::X, 0).into(), Syn::REPORT.into()]);
+    reader.append_events([AbsEvent::new(Abs::X, 7).into(), Syn::REPORT.into()]);
+    reader.append_events([
+        KeyEvent::new(Key::BTN_SOUTH, KeyState::PRESSED).into(),
+        Syn::REPORT.into(),
+    ]);
+    reader.append_events([
+        KeyEvent::new(Key::BTN_SOUTH, KeyState::REPEAT).into(),
+        Syn::REPORT.into(),
+    ]);
+    // Changes that are undone within the same report are skipped.
+    reader.append_events([
+        AbsEvent::new(Abs::X, 8).into(),
+        AbsEvent::new(Abs::X, 7).into(),
+        Syn::REPORT.into(),
+    ]);
+
+    let report = reader.imp.next_changing_report(&mut reader.test, &filter)?;
+    check_events(
+        report,
+        [AbsEvent::new(Abs::X, 7).into(), Syn::REPORT.into()],
+    );
+    let report = reader.imp.next_changing_report(&mut reader.test, &filter)?;
+    check_events(
+        report,
+        [
+            KeyEvent::new(Key::BTN_SOUTH, KeyState::PRESSED).into(),
+            Syn::REPORT.into(),
+        ],
+    );
+    let err = reader
+        .imp
+        .next_changing_report(&mut reader.test, &filter)
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::WouldBlock);
+
+    // Skipped reports still update the state.
+    assert_eq!(reader.imp.abs_state(Abs::Y), 1);
+    assert!(reader.imp.state.keys.contains(Key::BTN_EAST));
+
+    Ok(())
+}
+
+#[test]
+fn export_state() -> io::Result<()> {
+    let mut reader = EventReaderTest::new();
+    reader.imp.state.abs_axes = BitSet::from_iter([Abs::X, Abs::MT_SLOT]);
+    reader.append_events([
+        AbsEvent::new(Abs::X, 12).into(),
+        KeyEvent::new(Key::BTN_LEFT, KeyState::PRESSED).into(),
+        Syn::REPORT.into(),
+    ]);
+    reader.next_report()?;
+
+    let state = InputState::new(&reader.imp.state);
+    assert_eq!(state.keys(), &BitSet::from_iter([Key::BTN_LEFT]));
+    assert!(state.leds().is_empty());
+    assert_eq!(state.abs(Abs::X), Some(12));
+    assert_eq!(state.abs(Abs::Y), None);
+    assert_eq!(state.abs(Abs::MT_SLOT), None);
+    assert_eq!(state.abs_values().collect::<Vec<_>>(), [(Abs::X, 12)]);
+    assert_eq!(state.slots().slots().count(), 0);
+
+    // The exported state doesn't change when more events are processed.
+    reader.append_events([AbsEvent::new(Abs::X, 13).into(), Syn::REPORT.into()]);
+    reader.next_report()?;
+    assert_eq!(state.abs(Abs::X), Some(12));
+    assert_ne!(InputState::new(&reader.imp.state), state);
+
+    Ok(())
+}
+
+#[test]
+fn next_report_into() -> io::Result<()> {
+    let mut reader = EventReaderTest::new();
+    reader.append_events([
+        KeyEvent::new(Key::KEY_A, KeyState::PRESSED).into(),
+        Syn::REPORT.into(),
+    ]);
+    reader.append_events([RelEvent::new(Rel::DIAL, 1).into(), Syn::REPORT.into()]);
+
+    let mut out = vec![RelEvent::new(Rel::DIAL, 0).into()];
+    reader.imp.next_report_into(&mut reader.test, &mut out)?;
+    assert!(reader.imp.state.keys.contains(Key::KEY_A));
+    reader.imp.next_report_into(&mut reader.test, &mut out)?;
+    assert_eq!(
+        out,
+        [
+            RelEvent::new(Rel::DIAL, 0).into(),
+            KeyEvent::new(Key::KEY_A, KeyState::PRESSED).into(),
+            Syn::REPORT.into(),
+            RelEvent::new(Rel::DIAL, 1).into(),
+            Syn::REPORT.into(),
+        ]
+    );
+
+    let err = reader
+        .imp
+        .next_report_into(&mut reader.test, &mut out)
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::WouldBlock);
+    assert_eq!(out.len(), 5);
+    Ok(())
+}
+
+#[test]
+fn syn_timeout() -> io::Result<()> {
+    let mut reader = EventReaderTest::new();
+    reader.append_events([RelEvent::new(Rel::DIAL, 1).into()]);
+
+    // Without a timeout, the incomplete report is held back.
+    let err = reader.next_report().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::WouldBlock);
+
+    reader.imp.syn_timeout = Some(Duration::from_millis(10));
+    reader.append_events([RelEvent::new(Rel::DIAL, 2).into()]);
+    let report = reader.next_report()?;
+    check_events(
+        report,
+        [
+            RelEvent::new(Rel::DIAL, 1).into(),
+            RelEvent::new(Rel::DIAL, 2).into(),
+            Syn::REPORT.into(),
+        ],
+    );
+
+    // Complete reports are passed through unchanged.
+    reader.append_events([RelEvent::new(Rel::DIAL, 3).into(), Syn::REPORT.into()]);
+    let report = reader.next_report()?;
+    check_events(
+        report,
+        [RelEvent::new(Rel::DIAL, 3).into(), Syn::REPORT.into()],
+    );
+    Ok(())
+}
+
+#[test]
+fn syn_timeout_nonblocking() -> io::Result<()> {
+    let mut reader = EventReaderTest::new();
+    reader.test.nonblocking = true;
+    reader.imp.syn_timeout = Some(Duration::from_millis(50));
+    reader.append_events([RelEvent::new(Rel::DIAL, 1).into()]);
+
+    // Before the deadline, reads don't wait and report that no complete report is available.
+    let err = reader.next_report().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::WouldBlock);
+    let err = reader.next_report().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::WouldBlock);
+
+    std::thread::sleep(Duration::from_millis(50));
+    let report = reader.next_report()?;
+    check_events(
+        report,
+        [RelEvent::new(Rel::DIAL, 1).into(), Syn::REPORT.into()],
+    );
+
+    // The deadline restarts with the next incomplete report.
+    reader.append_events([RelEvent::new(Rel::DIAL, 2).into()]);
+    let err = reader.next_report().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::WouldBlock);
+    Ok(())
+}
+
+#[test]
+fn report_kinds() -> io::Result<()> {
+    let mut reader = EventReaderTest::new();
+    reader.append_events([
+        RelEvent::new(Rel::DIAL, 1).into(),
+        KeyEvent::new(Key::KEY_A, KeyState::PRESSED).into(),
+        Syn::REPORT.into(),
+    ]);
+
+    let report = reader.next_report()?;
+    let kinds: Vec<_> = report.kinds().collect();
+    assert_eq!(
+        kinds,
+        [
+            EventKind::Rel(RelEvent::new(Rel::DIAL, 1)),
+            EventKind::Key(KeyEvent::new(Key::KEY_A, KeyState::PRESSED)),
+            EventKind::Syn(SynEvent::new(Syn::REPORT)),
+        ]
+    );
+    Ok(())
+}
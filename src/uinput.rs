@@ -127,23 +127,27 @@
 //! [`ForceFeedbackEvent`]: crate::event::ForceFeedbackEvent
 
 mod r#async;
+mod touch;
 
 #[cfg_attr(docsrs, doc(cfg(any(feature = "tokio", feature = "async-io"))))]
 #[cfg(any(feature = "tokio", feature = "async-io"))]
 pub use r#async::AsyncEvents;
+pub use touch::TouchEmulator;
 
 use std::{
     error::Error,
     ffi::{CStr, CString, OsString, c_char, c_int},
     fmt,
-    fs::File,
+    fs::{self, File},
     io, mem,
     os::{
         fd::{AsFd, AsRawFd, BorrowedFd, IntoRawFd, OwnedFd},
         unix::{ffi::OsStringExt, prelude::RawFd},
     },
+    path::{Path, PathBuf},
     ptr, slice,
-    time::Instant,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
 };
 
 use uoctl::Ioctl;
@@ -151,10 +155,11 @@ use uoctl::Ioctl;
 use crate::{
     AbsInfo, InputId, InputProp, KeyRepeat, Slot,
     batch::BatchWriter,
+    bits::{BitSet, BitValue},
     drop::on_drop,
     event::{
-        Abs, AbsEvent, EventType, InputEvent, Key, Led, Misc, Rel, Repeat, RepeatEvent, Sound,
-        Switch, Syn, SynEvent, UinputCode, UinputEvent,
+        Abs, AbsEvent, EventType, InputEvent, Key, KeyEvent, KeyState, Led, Misc, Rel, RelEvent,
+        Repeat, RepeatEvent, Sound, Switch, Syn, SynEvent, UinputCode, UinputEvent,
     },
     ff::{self, Effect, EffectId},
     raw::{
@@ -168,7 +173,7 @@ use crate::{
         },
     },
     read_raw,
-    util::{block_until_readable, errorkind2libc, is_readable, set_nonblocking},
+    util::{block_until_readable, errorkind2libc, is_readable, set_cloexec, set_nonblocking},
 };
 
 /// Absolute axis setup information.
@@ -211,12 +216,99 @@ impl AbsSetup {
     }
 }
 
+/// The capabilities a [`UinputDevice`] was configured with.
+///
+/// Returned by [`UinputDevice::capabilities`].
+///
+/// This records what was passed to the [`Builder`] methods, so that the creator of a
+/// [`UinputDevice`] can inspect it without opening the `evdev` end of the device.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Capabilities {
+    events: BitSet<EventType>,
+    props: BitSet<InputProp>,
+    keys: BitSet<Key>,
+    rel: BitSet<Rel>,
+    abs: BitSet<Abs>,
+    misc: BitSet<Misc>,
+    leds: BitSet<Led>,
+    sounds: BitSet<Sound>,
+    switches: BitSet<Switch>,
+    ff_features: BitSet<ff::Feature>,
+}
+
+impl Capabilities {
+    /// Returns the set of enabled [`EventType`]s.
+    ///
+    /// This includes [`EventType::FF`] if [`Builder::with_ff_effects_max`] was called with a
+    /// non-zero value.
+    /// It does not include [`EventType::SYN`], which the kernel enables for every device.
+    #[inline]
+    pub fn events(&self) -> &BitSet<EventType> {
+        &self.events
+    }
+
+    /// Returns the set of [`InputProp`]s set via [`Builder::with_props`].
+    #[inline]
+    pub fn props(&self) -> &BitSet<InputProp> {
+        &self.props
+    }
+
+    /// Returns the set of [`Key`]s enabled via [`Builder::with_keys`].
+    #[inline]
+    pub fn keys(&self) -> &BitSet<Key> {
+        &self.keys
+    }
+
+    /// Returns the set of [`Rel`] axes enabled via [`Builder::with_rel_axes`].
+    #[inline]
+    pub fn rel_axes(&self) -> &BitSet<Rel> {
+        &self.rel
+    }
+
+    /// Returns the set of [`Abs`] axes enabled via [`Builder::with_abs_axes`].
+    #[inline]
+    pub fn abs_axes(&self) -> &BitSet<Abs> {
+        &self.abs
+    }
+
+    /// Returns the set of [`Misc`] events enabled via [`Builder::with_misc`].
+    #[inline]
+    pub fn misc(&self) -> &BitSet<Misc> {
+        &self.misc
+    }
+
+    /// Returns the set of [`Led`]s enabled via [`Builder::with_leds`].
+    #[inline]
+    pub fn leds(&self) -> &BitSet<Led> {
+        &self.leds
+    }
+
+    /// Returns the set of [`Sound`]s enabled via [`Builder::with_sounds`].
+    #[inline]
+    pub fn sounds(&self) -> &BitSet<Sound> {
+        &self.sounds
+    }
+
+    /// Returns the set of [`Switch`]es enabled via [`Builder::with_switches`].
+    #[inline]
+    pub fn switches(&self) -> &BitSet<Switch> {
+        &self.switches
+    }
+
+    /// Returns the set of force-feedback features advertised via [`Builder::with_ff_features`].
+    #[inline]
+    pub fn ff_features(&self) -> &BitSet<ff::Feature> {
+        &self.ff_features
+    }
+}
+
 /// A builder for creating a [`UinputDevice`].
 ///
 /// Returned by [`UinputDevice::builder`].
 pub struct Builder {
     device: UinputDevice, // handle to `/dev/uinput`
     setup: uinput_setup,
+    ff_features: bool,
 }
 
 impl fmt::Debug for Builder {
@@ -230,21 +322,32 @@ impl fmt::Debug for Builder {
 }
 
 impl Builder {
-    fn new() -> io::Result<Self> {
+    fn new(path: &Path) -> io::Result<Self> {
         let file = File::options()
             .read(true)
             .write(true)
-            .open("/dev/uinput")
-            .map_err(|e| io::Error::new(e.kind(), format!("failed to open '/dev/uinput': {e}")))?;
-        let device = UinputDevice { file };
+            .open(path)
+            .map_err(|e| {
+                io::Error::new(
+                    e.kind(),
+                    format!("failed to open '{}': {e}", path.display()),
+                )
+            })?;
+        let device = UinputDevice {
+            file,
+            ff_effects_max: 0,
+            caps: Capabilities::default(),
+            effects: Arc::default(),
+        };
         unsafe {
             let mut version = 0;
             device.ioctl("UI_GET_VERSION", UI_GET_VERSION, &mut version)?;
-            debug!("opened /dev/uinput; version={version:#x}");
+            debug!("opened {}; version={version:#x}", path.display());
         }
         Ok(Self {
             device,
             setup: unsafe { mem::zeroed() },
+            ff_features: false,
         })
     }
 
@@ -291,49 +394,38 @@ impl Builder {
     ///
     /// [`InputProp`]s can be used to advertise a specific type of device, like a drawing tablet.
     #[doc(alias = "UI_SET_PROPBIT")]
-    pub fn with_props(self, props: impl IntoIterator<Item = InputProp>) -> io::Result<Self> {
+    pub fn with_props(mut self, props: impl IntoIterator<Item = InputProp>) -> io::Result<Self> {
         for prop in props {
             unsafe {
                 self.device
                     .ioctl("UI_SET_PROPBIT", UI_SET_PROPBIT, prop.0.into())?;
             }
+            self.device.caps.props.insert(prop);
         }
         Ok(self)
     }
 
     /// Enables the given list of [`Key`]s to be reported by the device.
     #[doc(alias = "UI_SET_KEYBIT")]
-    pub fn with_keys(self, keys: impl IntoIterator<Item = Key>) -> io::Result<Self> {
-        self.enable_codes(
-            "UI_SET_KEYBIT",
-            UI_SET_KEYBIT,
-            EventType::KEY,
-            keys.into_iter().map(|v| v.raw().into()),
-        )?;
+    pub fn with_keys(mut self, keys: impl IntoIterator<Item = Key>) -> io::Result<Self> {
+        let enabled = self.enable_codes("UI_SET_KEYBIT", UI_SET_KEYBIT, EventType::KEY, keys)?;
+        self.device.caps.keys.extend(enabled);
         Ok(self)
     }
 
     /// Enables the given list of [`Rel`]ative axes to be reported by the device.
     #[doc(alias = "UI_SET_RELBIT")]
-    pub fn with_rel_axes(self, rel: impl IntoIterator<Item = Rel>) -> io::Result<Self> {
-        self.enable_codes(
-            "UI_SET_RELBIT",
-            UI_SET_RELBIT,
-            EventType::REL,
-            rel.into_iter().map(|v| v.raw().into()),
-        )?;
+    pub fn with_rel_axes(mut self, rel: impl IntoIterator<Item = Rel>) -> io::Result<Self> {
+        let enabled = self.enable_codes("UI_SET_RELBIT", UI_SET_RELBIT, EventType::REL, rel)?;
+        self.device.caps.rel.extend(enabled);
         Ok(self)
     }
 
     /// Enables the given list of [`Misc`] events to be reported by the device.
     #[doc(alias = "UI_SET_MSCBIT")]
-    pub fn with_misc(self, misc: impl IntoIterator<Item = Misc>) -> io::Result<Self> {
-        self.enable_codes(
-            "UI_SET_MSCBIT",
-            UI_SET_MSCBIT,
-            EventType::MSC,
-            misc.into_iter().map(|v| v.raw().into()),
-        )?;
+    pub fn with_misc(mut self, misc: impl IntoIterator<Item = Misc>) -> io::Result<Self> {
+        let enabled = self.enable_codes("UI_SET_MSCBIT", UI_SET_MSCBIT, EventType::MSC, misc)?;
+        self.device.caps.misc.extend(enabled);
         Ok(self)
     }
 
@@ -342,13 +434,9 @@ impl Builder {
     /// LEDs may be controlled by either the `uinput` or `evdev` side, by writing the appropriate
     /// event to the stream.
     #[doc(alias = "UI_SET_LEDBIT")]
-    pub fn with_leds(self, leds: impl IntoIterator<Item = Led>) -> io::Result<Self> {
-        self.enable_codes(
-            "UI_SET_LEDBIT",
-            UI_SET_LEDBIT,
-            EventType::LED,
-            leds.into_iter().map(|v| v.raw().into()),
-        )?;
+    pub fn with_leds(mut self, leds: impl IntoIterator<Item = Led>) -> io::Result<Self> {
+        let enabled = self.enable_codes("UI_SET_LEDBIT", UI_SET_LEDBIT, EventType::LED, leds)?;
+        self.device.caps.leds.extend(enabled);
         Ok(self)
     }
 
@@ -357,25 +445,17 @@ impl Builder {
     /// Sounds are typically played by an [`Evdev`][crate::Evdev] handle by writing the appropriate
     /// event to the stream.
     #[doc(alias = "UI_SET_SNDBIT")]
-    pub fn with_sounds(self, sounds: impl IntoIterator<Item = Sound>) -> io::Result<Self> {
-        self.enable_codes(
-            "UI_SET_SNDBIT",
-            UI_SET_SNDBIT,
-            EventType::SND,
-            sounds.into_iter().map(|v| v.raw().into()),
-        )?;
+    pub fn with_sounds(mut self, sounds: impl IntoIterator<Item = Sound>) -> io::Result<Self> {
+        let enabled = self.enable_codes("UI_SET_SNDBIT", UI_SET_SNDBIT, EventType::SND, sounds)?;
+        self.device.caps.sounds.extend(enabled);
         Ok(self)
     }
 
     /// Enables the given list of [`Switch`]es to be reported by the device.
     #[doc(alias = "UI_SET_SWBIT")]
-    pub fn with_switches(self, switches: impl IntoIterator<Item = Switch>) -> io::Result<Self> {
-        self.enable_codes(
-            "UI_SET_SWBIT",
-            UI_SET_SWBIT,
-            EventType::SW,
-            switches.into_iter().map(|v| v.raw().into()),
-        )?;
+    pub fn with_switches(mut self, switches: impl IntoIterator<Item = Switch>) -> io::Result<Self> {
+        let enabled = self.enable_codes("UI_SET_SWBIT", UI_SET_SWBIT, EventType::SW, switches)?;
+        self.device.caps.switches.extend(enabled);
         Ok(self)
     }
 
@@ -384,7 +464,7 @@ impl Builder {
     /// The [`AbsInfo`] associated with an axis may be changed by an [`Evdev`][crate::Evdev] client
     /// via [`Evdev::set_abs_info`][crate::Evdev::set_abs_info].
     #[doc(alias = "UI_SET_ABSBIT", alias = "UI_ABS_SETUP")]
-    pub fn with_abs_axes(self, axes: impl IntoIterator<Item = AbsSetup>) -> io::Result<Self> {
+    pub fn with_abs_axes(mut self, axes: impl IntoIterator<Item = AbsSetup>) -> io::Result<Self> {
         self.enable_event(EventType::ABS)?;
         for setup in axes {
             unsafe {
@@ -392,6 +472,7 @@ impl Builder {
                     .ioctl("UI_SET_ABSBIT", UI_SET_ABSBIT, setup.0.code as c_int)?;
                 self.device.ioctl("UI_ABS_SETUP", UI_ABS_SETUP, &setup.0)?;
             }
+            self.device.caps.abs.insert(setup.abs());
         }
         Ok(self)
     }
@@ -412,16 +493,16 @@ impl Builder {
     /// Advertises the given force-feedback capabilities.
     ///
     /// If you call this method, you also have to call [`Builder::with_ff_effects_max`] to configure
-    /// the maximum number of force-feedback effects the device can accept, or the functionality
-    /// won't work.
+    /// the maximum number of force-feedback effects the device can accept, or [`Builder::build`]
+    /// will fail.
     #[doc(alias = "UI_SET_FFBIT")]
-    pub fn with_ff_features(self, feat: impl IntoIterator<Item = ff::Feature>) -> io::Result<Self> {
-        self.enable_codes(
-            "UI_SET_FFBIT",
-            UI_SET_FFBIT,
-            EventType::FF,
-            feat.into_iter().map(|v| v.0.into()),
-        )?;
+    pub fn with_ff_features(
+        mut self,
+        feat: impl IntoIterator<Item = ff::Feature>,
+    ) -> io::Result<Self> {
+        let enabled = self.enable_codes("UI_SET_FFBIT", UI_SET_FFBIT, EventType::FF, feat)?;
+        self.device.caps.ff_features.extend(enabled);
+        self.ff_features = true;
         Ok(self)
     }
 
@@ -432,35 +513,51 @@ impl Builder {
     /// This will also allow [`Evdev`][crate::Evdev] clients to query and modify the key repeat
     /// settings via [`Evdev::key_repeat`][crate::Evdev::key_repeat] and
     /// [`Evdev::set_key_repeat`][crate::Evdev::set_key_repeat].
-    pub fn with_key_repeat(self) -> io::Result<Self> {
+    pub fn with_key_repeat(mut self) -> io::Result<Self> {
         // NOTE: cannot take the `KeyRepeat` as an argument because it has to be written to the stream
         self.enable_event(EventType::REP)?;
         Ok(self)
     }
 
+    /// Enables support for an [`EventType`] without enabling any event codes of that type.
+    ///
+    /// The `with_*` methods enable the matching [`EventType`] automatically, so this is only
+    /// needed to mirror devices that advertise an [`EventType`] with no codes.
+    #[doc(alias = "UI_SET_EVBIT")]
+    pub fn enable_event_type(mut self, event: EventType) -> io::Result<Self> {
+        self.enable_event(event)?;
+        Ok(self)
+    }
+
     // Will return `EINVAL` when attempting to enable a code above the maximum for that type of code.
-    fn enable_codes(
-        &self,
+    //
+    // Returns the set of codes that were enabled.
+    fn enable_codes<V: BitValue>(
+        &mut self,
         ioctl_name: &'static str,
         ioctl: Ioctl<c_int>,
         event: EventType,
-        codes: impl IntoIterator<Item = usize>,
-    ) -> io::Result<()> {
+        codes: impl IntoIterator<Item = V>,
+    ) -> io::Result<BitSet<V>> {
         // Note: these will all yield `EINVAL` with out-of-range indices
         self.enable_event(event)?;
+        let mut enabled = BitSet::new();
         for code in codes {
             unsafe {
-                self.device.ioctl(ioctl_name, ioctl, code as c_int)?;
+                self.device
+                    .ioctl(ioctl_name, ioctl, code.into_index() as c_int)?;
             }
+            enabled.insert(code);
         }
-        Ok(())
+        Ok(enabled)
     }
 
-    fn enable_event(&self, event: EventType) -> io::Result<()> {
+    fn enable_event(&mut self, event: EventType) -> io::Result<()> {
         unsafe {
             self.device
                 .ioctl("UI_SET_EVBIT", UI_SET_EVBIT, event.0 as c_int)?;
         }
+        self.device.caps.events.insert(event);
         Ok(())
     }
 
@@ -481,6 +578,12 @@ impl Builder {
     ///
     /// - `name`: The name of the device. Should be ASCII, and must not be longer than 79 bytes, or
     ///   this method will return an error.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`io::ErrorKind::InvalidInput`] error if [`Builder::with_ff_features`] was
+    /// called without also calling [`Builder::with_ff_effects_max`] (the kernel would otherwise
+    /// reject the device with a less descriptive `EINVAL` error).
     #[doc(alias = "UI_DEV_SETUP")]
     pub fn build(mut self, name: &str) -> io::Result<UinputDevice> {
         if name.len() >= UINPUT_MAX_NAME_SIZE {
@@ -489,6 +592,12 @@ impl Builder {
                 "uinput device name is too long",
             ));
         }
+        if self.ff_features && self.setup.ff_effects_max == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "force-feedback features were enabled, but `Builder::with_ff_effects_max` was not called",
+            ));
+        }
 
         unsafe {
             ptr::copy_nonoverlapping(
@@ -503,10 +612,59 @@ impl Builder {
                 .ioctl("UI_DEV_SETUP", UI_DEV_SETUP, &self.setup)?;
             UI_DEV_CREATE.ioctl(&self.device)?;
         }
+        self.device.ff_effects_max = self.setup.ff_effects_max;
+        if self.device.ff_effects_max > 0 {
+            // The kernel enables `EV_FF` for devices that support force-feedback effects.
+            self.device.caps.events.insert(EventType::FF);
+        }
         Ok(self.device)
     }
 }
 
+/// Optional record of the force-feedback effects uploaded to a [`UinputDevice`] (or one of its
+/// clones).
+///
+/// `None` if tracking is disabled (see [`UinputDevice::track_ff_effects`]).
+/// Shared between all clones, so that the warning is only logged when the last of them is dropped.
+#[derive(Debug, Default)]
+struct EffectTracker(Mutex<Option<Vec<EffectId>>>);
+
+impl EffectTracker {
+    fn with(&self, f: impl FnOnce(&mut Vec<EffectId>)) {
+        let mut effects = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(effects) = &mut *effects {
+            f(effects);
+        }
+    }
+
+    fn uploaded(&self, id: EffectId) {
+        // Uploads can also modify an existing effect.
+        self.with(|effects| {
+            if !effects.contains(&id) {
+                effects.push(id);
+            }
+        });
+    }
+
+    fn erased(&self, id: EffectId) {
+        self.with(|effects| effects.retain(|&e| e != id));
+    }
+
+    /// Disarms the warning, for when the file descriptor outlives the [`UinputDevice`].
+    fn forget(&self) {
+        self.with(Vec::clear);
+    }
+}
+
+impl Drop for EffectTracker {
+    fn drop(&mut self) {
+        let effects = self.0.get_mut().unwrap_or_else(|e| e.into_inner());
+        if let Some(effects) = effects.as_ref().filter(|effects| !effects.is_empty()) {
+            warn!("`UinputDevice` dropped with force-feedback effects still uploaded: {effects:?}");
+        }
+    }
+}
+
 /// A virtual `uinput` device.
 ///
 /// Please refer to the [module documentation][self] for more information on how to use this.
@@ -517,6 +675,9 @@ pub struct UinputDevice {
     // Closing the last handle to the device will already make the kernel clean everything up
     // anyways, so using the ioctl seems unnecessary.
     file: File,
+    ff_effects_max: u32,
+    caps: Capabilities,
+    effects: Arc<EffectTracker>,
 }
 
 impl AsFd for UinputDevice {
@@ -536,6 +697,7 @@ impl AsRawFd for UinputDevice {
 impl IntoRawFd for UinputDevice {
     #[inline]
     fn into_raw_fd(self) -> RawFd {
+        self.effects.forget();
         self.file.into_raw_fd()
     }
 }
@@ -543,6 +705,7 @@ impl IntoRawFd for UinputDevice {
 impl From<UinputDevice> for OwnedFd {
     #[inline]
     fn from(value: UinputDevice) -> Self {
+        value.effects.forget();
         value.file.into()
     }
 }
@@ -554,8 +717,29 @@ impl UinputDevice {
     ///
     /// This will fail with an [`io::ErrorKind::PermissionDenied`] error if the user is not
     /// allowed to open `/dev/uinput` with read and write permission.
+    ///
+    /// `/dev/uinput` is opened with `O_CLOEXEC`, so the resulting [`UinputDevice`] will not be
+    /// inherited by child processes spawned via `exec` (see [`UinputDevice::set_cloexec`]).
     pub fn builder() -> io::Result<Builder> {
-        Builder::new()
+        Builder::new(Path::new("/dev/uinput"))
+    }
+
+    /// Returns a [`Builder`] for configuring a new input device, using the `uinput` control node
+    /// at `path`.
+    ///
+    /// This works like [`UinputDevice::builder`], but allows opening the `uinput` node at a
+    /// non-standard location, like `/dev/input/uinput` or a path it was bind-mounted to.
+    ///
+    /// `path` must refer to the `uinput` character device.
+    /// The `UI_GET_VERSION` ioctl is used to check this, but since ioctl numbers of different
+    /// drivers can collide, `path` should not be taken from untrusted input.
+    ///
+    /// # Errors
+    ///
+    /// This will fail with an [`io::ErrorKind::PermissionDenied`] error if the user is not
+    /// allowed to open `path` with read and write permission.
+    pub fn builder_at(path: impl AsRef<Path>) -> io::Result<Builder> {
+        Builder::new(path.as_ref())
     }
 
     /// Creates a [`UinputDevice`] instance from a bare file descriptor.
@@ -565,10 +749,16 @@ impl UinputDevice {
     /// `owned_fd` must refer to a uinput character device (not to an `evdev`!).
     /// If it doesn't, the uinput ioctls will be sent to the wrong driver, which may have a
     /// colliding ioctl number with memory-unsafe semantics when invoked this way.
+    ///
+    /// The close-on-exec flag of `owned_fd` is left unchanged (see
+    /// [`UinputDevice::set_cloexec`]).
     #[inline]
     pub unsafe fn from_owned_fd(owned_fd: OwnedFd) -> Self {
         Self {
             file: owned_fd.into(),
+            ff_effects_max: 0,
+            caps: Capabilities::default(),
+            effects: Arc::default(),
         }
     }
 
@@ -585,6 +775,17 @@ impl UinputDevice {
         set_nonblocking(self.as_raw_fd(), nonblocking)
     }
 
+    /// Sets or clears the close-on-exec flag (`FD_CLOEXEC`) of this handle's file descriptor.
+    ///
+    /// Returns whether the flag was previously set.
+    ///
+    /// [`UinputDevice::builder`] sets the flag by default, so that child processes spawned via
+    /// `exec` can't inject input events through this device.
+    #[doc(alias = "FD_CLOEXEC")]
+    pub fn set_cloexec(&self, cloexec: bool) -> io::Result<bool> {
+        set_cloexec(self.as_raw_fd(), cloexec)
+    }
+
     /// Creates a new [`UinputDevice`] instance that refers to the same underlying file handle.
     ///
     /// All properties, such as whether the handle is in non-blocking mode, will be shared between
@@ -593,6 +794,9 @@ impl UinputDevice {
     pub fn try_clone(&self) -> io::Result<Self> {
         Ok(Self {
             file: self.file.try_clone()?,
+            ff_effects_max: self.ff_effects_max,
+            caps: self.caps.clone(),
+            effects: self.effects.clone(),
         })
     }
 
@@ -669,6 +873,80 @@ impl UinputDevice {
         unsafe { self.fetch_string("UI_GET_SYSNAME", UI_GET_SYSNAME) }
     }
 
+    /// Returns whether this device will receive force-feedback requests.
+    ///
+    /// This is `true` if the device was configured with force-feedback support (via
+    /// [`Builder::with_ff_features`]) and the platform forwards force-feedback requests to uinput
+    /// devices.
+    /// Applications can use this to avoid setting up force-feedback handling that would never run.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// Force-feedback is stubbed out on FreeBSD, so this always returns `false` there.
+    pub fn supports_ff_requests(&self) -> bool {
+        if !crate::BackendCaps::current().force_feedback() {
+            return false;
+        }
+
+        match self.has_ff_features() {
+            Ok(ff) => ff,
+            Err(e) => {
+                debug!("failed to query force-feedback features of uinput device: {e}");
+                false
+            }
+        }
+    }
+
+    /// Returns the maximum number of force-feedback effects this device was configured with (via
+    /// [`Builder::with_ff_effects_max`]).
+    ///
+    /// If this is 0, the device will never receive force-feedback upload or erase requests.
+    ///
+    /// The kernel provides no way to query this value, so it is only known for devices created by
+    /// a [`Builder`] (and their clones made via [`UinputDevice::try_clone`]).
+    /// Devices created via [`UinputDevice::from_owned_fd`] always return 0.
+    #[inline]
+    pub fn ff_effects_max(&self) -> u32 {
+        self.ff_effects_max
+    }
+
+    /// Returns the [`Capabilities`] this device was configured with.
+    ///
+    /// Like [`UinputDevice::ff_effects_max`], this is only known for devices created by a
+    /// [`Builder`] (and their clones made via [`UinputDevice::try_clone`]).
+    /// Devices created via [`UinputDevice::from_owned_fd`] return empty [`Capabilities`].
+    #[inline]
+    pub fn capabilities(&self) -> &Capabilities {
+        &self.caps
+    }
+
+    /// Enables or disables tracking of uploaded force-feedback effects.
+    ///
+    /// When enabled, this device records which effects were successfully uploaded via
+    /// [`UinputDevice::ff_upload`] and not yet removed via [`UinputDevice::ff_erase`].
+    /// If the last [`UinputDevice`] handle is dropped while effects are still uploaded, a warning
+    /// listing them is logged, which helps diagnose clients that forget to erase their effects.
+    ///
+    /// The record is shared with all handles created via [`UinputDevice::try_clone`], so this
+    /// setting affects all of them, and effects may be uploaded and erased through different
+    /// handles.
+    ///
+    /// Tracking is disabled by default, since it adds a small cost to every upload.
+    /// Disabling it discards the recorded effects.
+    pub fn track_ff_effects(&mut self, track: bool) {
+        let mut effects = self.effects.0.lock().unwrap_or_else(|e| e.into_inner());
+        *effects = track.then(Vec::new);
+    }
+
+    /// Reads the device's force-feedback capabilities from sysfs.
+    fn has_ff_features(&self) -> io::Result<bool> {
+        let mut path = PathBuf::from("/sys/devices/virtual/input");
+        path.push(self.sysname()?);
+        path.push("capabilities/ff");
+        let caps = fs::read_to_string(path)?;
+        Ok(caps.chars().any(|c| c.is_ascii_hexdigit() && c != '0'))
+    }
+
     /// Returns an iterator over events *received* by this [`UinputDevice`].
     ///
     /// If the device exposes any of the following functionality, it should read events that trigger
@@ -695,6 +973,21 @@ impl UinputDevice {
     /// In both cases, the evdev client will block until [`UinputDevice::ff_upload`] or
     /// [`UinputDevice::ff_erase`] has been called.
     ///
+    /// # Echoed Events
+    ///
+    /// On Linux, any [`LedEvent`], [`SoundEvent`] or [`RepeatEvent`] that is written to the device
+    /// is also echoed back to the [`UinputDevice`], regardless of whether it was written by an
+    /// evdev client or by the [`UinputDevice`] itself (for example, via
+    /// [`EventWriter::set_key_repeat`]).
+    /// Unlike the events delivered to evdev clients, these events are *not* followed by a
+    /// `SYN_REPORT` event.
+    ///
+    /// Drivers that write LED, sound, or autorepeat events themselves should be prepared to
+    /// receive them back from this iterator.
+    /// [`UinputDevice::drain_echoes`] can be used to discard them, and
+    /// [`UinputDevice::classified_events`] can be used to tell them apart from force-feedback
+    /// requests.
+    ///
     /// [`LedEvent`]: crate::event::LedEvent
     /// [`SoundEvent`]: crate::event::SoundEvent
     /// [`ForceFeedbackEvent`]: crate::event::ForceFeedbackEvent
@@ -703,6 +996,43 @@ impl UinputDevice {
         Events { file: &self.file }
     }
 
+    /// Returns an iterator over incoming events that classifies them by what they request.
+    ///
+    /// This behaves like [`UinputDevice::events`], but yields [`Received`] values that
+    /// distinguish state changes (which may be echoes of events written by this device) from
+    /// force-feedback requests (which must be acted upon).
+    #[inline]
+    pub fn classified_events(&self) -> ClassifiedEvents<'_> {
+        ClassifiedEvents(self.events())
+    }
+
+    /// Reads all pending LED, sound, and autorepeat events without blocking.
+    ///
+    /// This is meant to be called after writing events that the kernel echoes back to the
+    /// [`UinputDevice`] (see [`UinputDevice::events`]), so that the driver's main loop doesn't
+    /// mistake them for requests from an evdev client.
+    ///
+    /// Reading stops once no more events are pending, or when an event is read that is not an LED,
+    /// sound, or autorepeat event (like a force-feedback request or playback event).
+    /// Such an event is not discarded, but returned separately via [`Echoes::pending`], and has
+    /// to be handled like any other event returned by [`UinputDevice::events`].
+    pub fn drain_echoes(&self) -> io::Result<Echoes> {
+        let mut echoes = Echoes::default();
+        while self.is_readable()? {
+            let Some(res) = self.classified_events().next() else {
+                break;
+            };
+            match res? {
+                Received::StateChange(ev) => echoes.echoes.push(ev),
+                Received::ForceFeedback(ev) | Received::Other(ev) => {
+                    echoes.pending = Some(ev);
+                    break;
+                }
+            }
+        }
+        Ok(echoes)
+    }
+
     /// Returns an async iterator over incoming events.
     ///
     /// The underlying device will be put in non-blocking mode while the returned [`AsyncEvents`]
@@ -780,6 +1110,12 @@ impl UinputDevice {
     /// This functionality is stubbed out on FreeBSD. [`UinputEvent`]s are never sent to the
     /// [`UinputDevice`].
     ///
+    /// # Errors
+    ///
+    /// If the process that requested the upload has stopped waiting for it (for example, because
+    /// the kernel's internal timeout of 30 seconds has expired), an error of type
+    /// [`io::ErrorKind::InvalidInput`] is returned, and `handler` is not invoked.
+    ///
     /// # Panics
     ///
     /// This method will panic if the given [`UinputEvent`] is not a force-feedback upload event.
@@ -789,6 +1125,56 @@ impl UinputDevice {
         request: &UinputEvent,
         handler: impl FnOnce(&ForceFeedbackUpload) -> io::Result<R>,
     ) -> io::Result<R> {
+        Ok(self.ff_upload_impl(request, None, handler)?)
+    }
+
+    /// Performs a requested force-feedback effect upload, giving up after `timeout`.
+    ///
+    /// This works like [`UinputDevice::ff_upload`], but bounds the time the requesting process
+    /// has to wait for the upload.
+    /// `timeout` is measured from the time the kernel sent the `request`, so requests that have
+    /// been waiting in the event queue count against it.
+    ///
+    /// If the request is already older than `timeout`, `handler` is not invoked.
+    /// `handler` itself cannot be interrupted, but if it finishes after `timeout` has expired, the
+    /// upload fails anyway.
+    /// In both cases, the requesting process is sent an `ETIMEDOUT` error, and an error of type
+    /// [`io::ErrorKind::TimedOut`] is returned.
+    ///
+    /// This is useful for devices that forward force-feedback effects to another device, which
+    /// may be slow to respond or have disappeared.
+    ///
+    /// # Errors
+    ///
+    /// Errors are returned as an [`FfUploadError`], which converts into an [`io::Error`].
+    /// If `handler` succeeded, but the upload failed anyway (because `handler` finished too late,
+    /// or the request could not be completed), [`FfUploadError::late_result`] returns its result,
+    /// so that the caller can undo any side effects of `handler` (for example, erase an effect it
+    /// uploaded to another device).
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if the given [`UinputEvent`] is not a force-feedback upload event.
+    pub fn ff_upload_timeout<R>(
+        &self,
+        request: &UinputEvent,
+        timeout: Duration,
+        handler: impl FnOnce(&ForceFeedbackUpload) -> io::Result<R>,
+    ) -> Result<R, FfUploadError<R>> {
+        // uinput timestamps its requests with `CLOCK_MONOTONIC`, like `Instant`.
+        let age = request
+            .latency_from(crate::util::clock_now(libc::CLOCK_MONOTONIC)?)
+            .unwrap_or(Duration::ZERO);
+        let deadline = Instant::now() + timeout.saturating_sub(age);
+        self.ff_upload_impl(request, Some(deadline), handler)
+    }
+
+    fn ff_upload_impl<R>(
+        &self,
+        request: &UinputEvent,
+        deadline: Option<Instant>,
+        handler: impl FnOnce(&ForceFeedbackUpload) -> io::Result<R>,
+    ) -> Result<R, FfUploadError<R>> {
         assert!(request.code() == UinputCode::FF_UPLOAD);
 
         let mut upload = unsafe { mem::zeroed::<ForceFeedbackUpload>() };
@@ -797,11 +1183,23 @@ impl UinputDevice {
         let now = Instant::now();
         let _d = on_drop(|| trace!("`ff_upload` took {:?}", now.elapsed()));
         unsafe {
-            self.ioctl("UI_BEGIN_FF_UPLOAD", UI_BEGIN_FF_UPLOAD, &mut upload.0)?;
+            self.ioctl("UI_BEGIN_FF_UPLOAD", UI_BEGIN_FF_UPLOAD, &mut upload.0)
+                .map_err(|e| stale_request_error(e, upload.0.request_id))?;
         }
 
-        let res = handler(&upload);
-        match &res {
+        let expired = || deadline.is_some_and(|deadline| Instant::now() >= deadline);
+        let res = if expired() {
+            Err(timeout_error(upload.0.request_id).into())
+        } else {
+            match handler(&upload) {
+                Ok(late) if expired() => Err(FfUploadError {
+                    error: timeout_error(upload.0.request_id),
+                    late: Some(late),
+                }),
+                res => res.map_err(FfUploadError::from),
+            }
+        };
+        match res.as_ref().map_err(|e| &e.error) {
             Ok(_) => {}
             Err(e) => {
                 let os_err = e.raw_os_error();
@@ -816,8 +1214,16 @@ impl UinputDevice {
             }
         }
 
-        unsafe {
-            self.ioctl("UI_END_FF_UPLOAD", UI_END_FF_UPLOAD, &upload.0)?;
+        if let Err(error) = unsafe { self.ioctl("UI_END_FF_UPLOAD", UI_END_FF_UPLOAD, &upload.0) } {
+            // The handler's result (if any) is of no use to the requesting process anymore.
+            let late = match res {
+                Ok(r) => Some(r),
+                Err(e) => e.late,
+            };
+            return Err(FfUploadError { error, late });
+        }
+        if res.is_ok() {
+            self.effects.uploaded(upload.effect_id());
         }
 
         res
@@ -835,6 +1241,12 @@ impl UinputDevice {
     /// This functionality is stubbed out on FreeBSD. [`UinputEvent`]s are never sent to the
     /// [`UinputDevice`].
     ///
+    /// # Errors
+    ///
+    /// Like [`UinputDevice::ff_upload`], this returns an error of type
+    /// [`io::ErrorKind::InvalidInput`] if the requesting process has stopped waiting for the
+    /// erasure.
+    ///
     /// # Panics
     ///
     /// This method will panic if the given [`UinputEvent`] is not a force-feedback erase event.
@@ -849,7 +1261,8 @@ impl UinputDevice {
         let mut erase = unsafe { mem::zeroed::<ForceFeedbackErase>() };
         erase.0.request_id = request.raw_value() as u32;
         unsafe {
-            self.ioctl("UI_BEGIN_FF_ERASE", UI_BEGIN_FF_ERASE, &mut erase.0)?;
+            self.ioctl("UI_BEGIN_FF_ERASE", UI_BEGIN_FF_ERASE, &mut erase.0)
+                .map_err(|e| stale_request_error(e, erase.0.request_id))?;
         }
 
         match handler(&erase) {
@@ -870,6 +1283,9 @@ impl UinputDevice {
         unsafe {
             self.ioctl("UI_END_FF_ERASE", UI_END_FF_ERASE, &erase.0)?;
         }
+        if erase.0.retval == 0 {
+            self.effects.erased(erase.effect_id());
+        }
 
         Ok(())
     }
@@ -890,6 +1306,11 @@ impl UinputDevice {
     /// [`RelEvent`]s will always be forwarded to readers (as long as their [`Rel`] axis has been
     /// enabled during construction), since there is no state associated with them.
     ///
+    /// # Panics
+    ///
+    /// In debug builds, this panics if `events` contains a [`UinputEvent`] or a `SYN_DROPPED`
+    /// event, since writing those to a device is always a bug.
+    ///
     /// [`RelEvent`]: crate::event::RelEvent
     pub fn write_events(&self, events: &[InputEvent]) -> io::Result<()> {
         self.writer().write_events(events)?.finish()?;
@@ -903,17 +1324,64 @@ impl UinputDevice {
     /// The same considerations as for [`UinputDevice::write_events`] apply to using the
     /// [`EventWriter`].
     pub fn writer(&self) -> EventWriter<'_> {
-        EventWriter {
-            file: &self.file,
-            batch: BatchWriter::new(),
-            needs_syn_report: true,
-        }
+        EventWriter::new(&self.file)
+    }
+
+    /// Presses `key` and sends the resulting report.
+    ///
+    /// `key` must have been enabled with [`Builder::with_keys`].
+    /// Pressing a key that is already pressed does nothing.
+    pub fn press_key(&self, key: Key) -> io::Result<()> {
+        self.write_events(&[KeyEvent::new(key, KeyState::PRESSED).into()])
+    }
+
+    /// Releases `key` and sends the resulting report.
+    ///
+    /// `key` must have been enabled with [`Builder::with_keys`].
+    /// Releasing a key that isn't pressed does nothing.
+    pub fn release_key(&self, key: Key) -> io::Result<()> {
+        self.write_events(&[KeyEvent::new(key, KeyState::RELEASED).into()])
+    }
+
+    /// Presses and then releases `key`.
+    ///
+    /// The press and the release are sent as 2 separate reports, so that readers observe the key
+    /// being held down.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use evdevil::{event::Key, uinput::UinputDevice};
+    ///
+    /// let dev = UinputDevice::builder()?
+    ///     .with_keys([Key::KEY_Q])?
+    ///     .build("My Input Device")?;
+    ///
+    /// dev.tap_key(Key::KEY_Q)?;
+    /// # std::io::Result::Ok(())
+    /// ```
+    pub fn tap_key(&self, key: Key) -> io::Result<()> {
+        self.press_key(key)?;
+        self.release_key(key)
+    }
+
+    /// Moves the pointer by `dx` and `dy` in a single report.
+    ///
+    /// Requires that [`Rel::X`] and [`Rel::Y`] were enabled with [`Builder::with_rel_axes`].
+    /// Positive values move the pointer right and down.
+    pub fn move_pointer(&self, dx: i32, dy: i32) -> io::Result<()> {
+        self.write_events(&[
+            RelEvent::new(Rel::X, dx).into(),
+            RelEvent::new(Rel::Y, dy).into(),
+        ])
     }
 }
 
-/// Helper for writing a sequence of events to the uinput device.
+/// Helper for writing a sequence of events to a uinput or evdev device.
+///
+/// Returned by [`UinputDevice::writer`] and [`Evdev::writer`].
 ///
-/// Returned by [`UinputDevice::writer`].
+/// [`Evdev::writer`]: crate::Evdev::writer
 #[derive(Debug)]
 #[must_use = "must call `EventWriter::finish` to flush the event batch"]
 pub struct EventWriter<'a> {
@@ -923,6 +1391,14 @@ pub struct EventWriter<'a> {
 }
 
 impl<'a> EventWriter<'a> {
+    pub(crate) fn new(file: &'a File) -> Self {
+        Self {
+            file,
+            batch: BatchWriter::new(),
+            needs_syn_report: true,
+        }
+    }
+
     /// Writes raw events to the device.
     ///
     /// Events passed to this method may be buffered to improve performance.
@@ -944,6 +1420,42 @@ impl<'a> EventWriter<'a> {
         Ok(SlotWriter(self))
     }
 
+    /// Updates several multitouch slots as part of this event batch.
+    ///
+    /// For every `(slot, data)` pair in `slots`, this selects `slot` (like [`EventWriter::slot`])
+    /// and then invokes `f` with a [`SlotWriter`] for that slot and the associated `data`.
+    ///
+    /// This is a convenient way to update all contacts of a multitouch frame at once, for example
+    /// to emulate a multi-finger gesture.
+    /// All slot updates are part of the same report, which is ended by [`EventWriter::finish`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use evdevil::uinput::UinputDevice;
+    /// # let dev: UinputDevice = todo!();
+    /// let touches = [(0, 100, 200), (1, 300, 400)];
+    /// dev.writer()
+    ///     .slots(touches.map(|t| (t.0, t)), |w, (id, x, y)| {
+    ///         w.set_tracking_id(id)?.set_position(x, y)
+    ///     })?
+    ///     .finish()?;
+    /// # std::io::Result::Ok(())
+    /// ```
+    pub fn slots<S, T>(
+        mut self,
+        slots: impl IntoIterator<Item = (S, T)>,
+        mut f: impl FnMut(SlotWriter<'a>, T) -> io::Result<SlotWriter<'a>>,
+    ) -> io::Result<Self>
+    where
+        S: TryInto<Slot>,
+    {
+        for (slot, data) in slots {
+            self = f(self.slot(slot)?, data)?.finish_slot()?;
+        }
+        Ok(self)
+    }
+
     /// Changes the device's [`KeyRepeat`] configuration.
     ///
     /// Requires that [`Builder::with_key_repeat`] was called to enable the autorepeat
@@ -1018,6 +1530,13 @@ impl<'a> SlotWriter<'a> {
         Ok(self)
     }
 
+    /// Lifts the contact in this MT slot and returns the original [`EventWriter`].
+    ///
+    /// This sets the slot's [`Abs::MT_TRACKING_ID`] to -1, which marks the slot as unused.
+    pub fn clear(self) -> io::Result<EventWriter<'a>> {
+        self.set_tracking_id(-1)?.finish_slot()
+    }
+
     /// Write raw events to the device.
     ///
     /// Any `ABS_MT_*` events will be associated with this MT slot.
@@ -1061,6 +1580,185 @@ impl Iterator for Events<'_> {
     }
 }
 
+/// Events read by [`UinputDevice::drain_echoes`].
+#[derive(Debug, Clone, Default)]
+pub struct Echoes {
+    echoes: Vec<InputEvent>,
+    pending: Option<InputEvent>,
+}
+
+impl Echoes {
+    /// Returns the LED, sound, and autorepeat events that were read.
+    #[inline]
+    pub fn echoes(&self) -> &[InputEvent] {
+        &self.echoes
+    }
+
+    /// Returns the event that stopped the drain, if any.
+    ///
+    /// This is the first event that wasn't an LED, sound, or autorepeat event.
+    /// If it is a [`UinputEvent`], it has to be passed to [`UinputDevice::ff_upload`] or
+    /// [`UinputDevice::ff_erase`], or the requesting evdev client will block.
+    #[inline]
+    pub fn pending(&self) -> Option<InputEvent> {
+        self.pending
+    }
+}
+
+/// An [`InputEvent`] received by a [`UinputDevice`], classified by its purpose.
+///
+/// Yielded by [`ClassifiedEvents`].
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub enum Received {
+    /// An LED, sound, or autorepeat state change.
+    ///
+    /// These are either requested by an evdev client, or echoes of events written by the
+    /// [`UinputDevice`] itself.
+    /// There is no way to tell which of the two it is.
+    StateChange(InputEvent),
+    /// A force-feedback request.
+    ///
+    /// This is either a [`UinputEvent`] that has to be passed to [`UinputDevice::ff_upload`] or
+    /// [`UinputDevice::ff_erase`], or a [`ForceFeedbackEvent`] that controls effect playback.
+    ///
+    /// [`ForceFeedbackEvent`]: crate::event::ForceFeedbackEvent
+    ForceFeedback(InputEvent),
+    /// Any other event.
+    Other(InputEvent),
+}
+
+impl Received {
+    /// Classifies an [`InputEvent`] received from a [`UinputDevice`].
+    pub fn classify(event: InputEvent) -> Self {
+        match event.event_type() {
+            EventType::LED | EventType::SND | EventType::REP => Self::StateChange(event),
+            EventType::UINPUT | EventType::FF => Self::ForceFeedback(event),
+            _ => Self::Other(event),
+        }
+    }
+
+    /// Returns the underlying [`InputEvent`].
+    #[inline]
+    pub fn event(&self) -> InputEvent {
+        match self {
+            Self::StateChange(ev) | Self::ForceFeedback(ev) | Self::Other(ev) => *ev,
+        }
+    }
+}
+
+/// An iterator over the events received by a [`UinputDevice`], yielding [`Received`] values.
+///
+/// Returned by [`UinputDevice::classified_events`].
+///
+/// Blocking behavior matches [`Events`].
+#[derive(Debug)]
+pub struct ClassifiedEvents<'a>(Events<'a>);
+
+impl Iterator for ClassifiedEvents<'_> {
+    type Item = io::Result<Received>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.0.next()?.map(Received::classify))
+    }
+}
+
+/// Adds context to the error returned when beginning to process an outdated force-feedback request.
+///
+/// The kernel returns `EINVAL` when the request ID is no longer known, which happens if the
+/// requesting process timed out or went away.
+fn stale_request_error(e: io::Error, request_id: u32) -> io::Error {
+    if e.kind() != io::ErrorKind::InvalidInput {
+        return e;
+    }
+    io::Error::new(
+        e.kind(),
+        format!(
+            "force-feedback request {request_id} is no longer pending (the requesting process \
+             may have timed out or closed the device): {e}"
+        ),
+    )
+}
+
+/// Error returned by [`UinputDevice::ff_upload_timeout`].
+///
+/// Converts into an [`io::Error`], so it can be propagated with `?` from functions returning
+/// [`io::Result`].
+pub struct FfUploadError<R> {
+    error: io::Error,
+    late: Option<R>,
+}
+
+impl<R> FfUploadError<R> {
+    /// Returns the underlying [`io::Error`].
+    #[inline]
+    pub fn error(&self) -> &io::Error {
+        &self.error
+    }
+
+    /// Returns the [`io::ErrorKind`] of the underlying [`io::Error`].
+    #[inline]
+    pub fn kind(&self) -> io::ErrorKind {
+        self.error.kind()
+    }
+
+    /// Returns the result of the handler if it succeeded, but the upload failed anyway (for
+    /// example, because the handler finished after the timeout).
+    ///
+    /// The upload did not complete successfully for the requesting process, so any side effects
+    /// of the handler should be undone.
+    #[inline]
+    pub fn late_result(&self) -> Option<&R> {
+        self.late.as_ref()
+    }
+
+    /// Returns the result of the handler if it succeeded, but the upload failed anyway.
+    #[inline]
+    pub fn into_late_result(self) -> Option<R> {
+        self.late
+    }
+}
+
+impl<R> From<io::Error> for FfUploadError<R> {
+    fn from(error: io::Error) -> Self {
+        Self { error, late: None }
+    }
+}
+
+impl<R> From<FfUploadError<R>> for io::Error {
+    fn from(e: FfUploadError<R>) -> Self {
+        e.error
+    }
+}
+
+impl<R> fmt::Debug for FfUploadError<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FfUploadError")
+            .field("error", &self.error)
+            .field("late", &self.late.is_some())
+            .finish()
+    }
+}
+
+impl<R> fmt::Display for FfUploadError<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.error, f)
+    }
+}
+
+impl<R> Error for FfUploadError<R> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.error.source()
+    }
+}
+
+fn timeout_error(request_id: u32) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::TimedOut,
+        format!("timed out while handling force-feedback request {request_id}"),
+    )
+}
+
 /// Contains data about a force-feedback effect upload or update.
 ///
 /// See [`UinputDevice::ff_upload`].
@@ -1068,6 +1766,15 @@ impl Iterator for Events<'_> {
 pub struct ForceFeedbackUpload(uinput_ff_upload);
 
 impl ForceFeedbackUpload {
+    /// Returns the kernel-assigned ID of this upload request.
+    ///
+    /// This is the value of the [`UinputEvent`] that requested the upload, and can be used to
+    /// correlate uploads with their originating request when several are in flight.
+    #[inline]
+    pub fn request_id(&self) -> u32 {
+        self.0.request_id
+    }
+
     /// Returns the [`Effect`] that is being uploaded.
     #[inline]
     pub fn effect(&self) -> &Effect<'_> {
@@ -1098,7 +1805,7 @@ impl ForceFeedbackUpload {
 impl fmt::Debug for ForceFeedbackUpload {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("ForceFeedbackUpload")
-            .field("request_id", &self.0.request_id)
+            .field("request_id", &self.request_id())
             .field("effect", self.effect())
             .field("old", self.old())
             .finish()
@@ -1112,6 +1819,14 @@ impl fmt::Debug for ForceFeedbackUpload {
 pub struct ForceFeedbackErase(uinput_ff_erase);
 
 impl ForceFeedbackErase {
+    /// Returns the kernel-assigned ID of this erase request.
+    ///
+    /// This is the value of the [`UinputEvent`] that requested the erasure.
+    #[inline]
+    pub fn request_id(&self) -> u32 {
+        self.0.request_id
+    }
+
     /// Returns the [`EffectId`] of the effect that should be erased.
     #[inline]
     pub fn effect_id(&self) -> EffectId {
@@ -1125,8 +1840,73 @@ impl ForceFeedbackErase {
 impl fmt::Debug for ForceFeedbackErase {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("ForceFeedbackErase")
-            .field("request_id", &self.0.request_id)
+            .field("request_id", &self.request_id())
             .field("effect_id", &self.effect_id())
             .finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::{
+        event::{LedEvent, RelEvent},
+        test::pair,
+    };
+
+    use super::*;
+
+    #[test]
+    fn bare_event_type() -> io::Result<()> {
+        if !fs::exists("/dev/uinput")? {
+            eprintln!("`/dev/uinput` doesn't exist, probably running under QEMU");
+            return Ok(());
+        }
+
+        let (_uinput, evdev) = pair(|b| b.enable_event_type(EventType::MSC))?;
+        assert!(evdev.supported_events()?.contains(EventType::MSC));
+        assert!(evdev.supported_misc()?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn capabilities() -> io::Result<()> {
+        if !fs::exists("/dev/uinput")? {
+            eprintln!("`/dev/uinput` doesn't exist, probably running under QEMU");
+            return Ok(());
+        }
+
+        let (uinput, evdev) = pair(|b| {
+            b.with_keys([Key::BTN_LEFT, Key::BTN_RIGHT])?
+                .with_rel_axes([Rel::X, Rel::Y])?
+                .with_abs_axes([AbsSetup::new(Abs::WHEEL, AbsInfo::new(0, 100))])?
+                .with_props([InputProp::POINTER])
+        })?;
+        let caps = uinput.capabilities();
+        assert_eq!(*caps.keys(), evdev.supported_keys()?);
+        assert_eq!(*caps.rel_axes(), evdev.supported_rel_axes()?);
+        assert_eq!(*caps.abs_axes(), evdev.supported_abs_axes()?);
+        assert_eq!(*caps.props(), evdev.props()?);
+        assert!(caps.leds().is_empty());
+        assert_eq!(
+            *caps.events(),
+            BitSet::from_iter([EventType::KEY, EventType::REL, EventType::ABS]),
+        );
+        assert_eq!(uinput.try_clone()?.capabilities(), caps);
+        Ok(())
+    }
+
+    #[test]
+    fn classify() {
+        let ev = *LedEvent::new(Led::CAPSL, true);
+        assert!(matches!(Received::classify(ev), Received::StateChange(_)));
+        let ev = *RepeatEvent::new(Repeat::DELAY, 250);
+        assert!(matches!(Received::classify(ev), Received::StateChange(_)));
+        let ev = InputEvent::new(EventType::UINPUT, UinputCode::FF_UPLOAD.raw(), 1);
+        assert!(matches!(Received::classify(ev), Received::ForceFeedback(_)));
+        let ev = *RelEvent::new(Rel::DIAL, 1);
+        assert!(matches!(Received::classify(ev), Received::Other(_)));
+        assert_eq!(Received::classify(ev).event().raw_value(), 1);
+    }
+}
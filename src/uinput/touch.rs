@@ -0,0 +1,263 @@
+use std::io;
+
+use crate::{
+    Slot,
+    event::{Key, KeyEvent, KeyState},
+    uinput::UinputDevice,
+};
+
+/// Emulates a multitouch device using the type-B multitouch protocol.
+///
+/// [`TouchEmulator`] wraps a [`UinputDevice`] and keeps track of which slots have a contact in
+/// them.
+/// It assigns tracking IDs to new contacts, releases them when the contact is lifted, and toggles
+/// [`Key::BTN_TOUCH`] and [`Key::BTN_TOOL_FINGER`] when the first contact is placed or the last
+/// one is lifted.
+/// Every method call emits a complete report.
+///
+/// The [`UinputDevice`] should be created with support for [`Abs::MT_SLOT`],
+/// [`Abs::MT_TRACKING_ID`], [`Abs::MT_POSITION_X`], and [`Abs::MT_POSITION_Y`], as well as
+/// [`Key::BTN_TOUCH`] and [`Key::BTN_TOOL_FINGER`].
+/// Single-touch pointer emulation (`ABS_X`/`ABS_Y`) is not performed.
+///
+/// # Example
+///
+/// ```no_run
+/// use evdevil::uinput::{TouchEmulator, UinputDevice};
+/// # let device: UinputDevice = todo!();
+///
+/// let mut touch = TouchEmulator::new(device, 4);
+/// touch.touch_down(0, 100, 200)?;
+/// touch.touch_move(0, 120, 210)?;
+/// touch.touch_up(0)?;
+/// # std::io::Result::Ok(())
+/// ```
+///
+/// [`Abs::MT_SLOT`]: crate::event::Abs::MT_SLOT
+/// [`Abs::MT_TRACKING_ID`]: crate::event::Abs::MT_TRACKING_ID
+/// [`Abs::MT_POSITION_X`]: crate::event::Abs::MT_POSITION_X
+/// [`Abs::MT_POSITION_Y`]: crate::event::Abs::MT_POSITION_Y
+#[derive(Debug)]
+pub struct TouchEmulator {
+    device: UinputDevice,
+    /// The tracking ID of the contact in each slot, or `None` if the slot is unused.
+    slots: Vec<Option<i32>>,
+    next_tracking_id: i32,
+}
+
+impl TouchEmulator {
+    /// Creates a [`TouchEmulator`] that writes to `device`, which has `slots` multitouch slots.
+    ///
+    /// `slots` should match the number of slots configured via [`Abs::MT_SLOT`] (its maximum
+    /// value plus one).
+    /// All slots are assumed to be unused.
+    ///
+    /// [`Abs::MT_SLOT`]: crate::event::Abs::MT_SLOT
+    pub fn new(device: UinputDevice, slots: u16) -> Self {
+        Self {
+            device,
+            slots: vec![None; slots.into()],
+            next_tracking_id: 0,
+        }
+    }
+
+    /// Returns a reference to the wrapped [`UinputDevice`].
+    #[inline]
+    pub fn device(&self) -> &UinputDevice {
+        &self.device
+    }
+
+    /// Returns the wrapped [`UinputDevice`].
+    ///
+    /// Any contacts that are still active will not be lifted.
+    #[inline]
+    pub fn into_device(self) -> UinputDevice {
+        self.device
+    }
+
+    /// Returns the number of contacts that are currently touching the device.
+    pub fn active_touches(&self) -> usize {
+        self.slots.iter().filter(|id| id.is_some()).count()
+    }
+
+    /// Places a new contact in `slot`, at position (`x`, `y`).
+    ///
+    /// Returns the tracking ID assigned to the contact.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of type [`io::ErrorKind::InvalidInput`] if `slot` is out of range or
+    /// already contains a contact.
+    pub fn touch_down(&mut self, slot: impl TryInto<Slot>, x: i32, y: i32) -> io::Result<i32> {
+        let (slot, index) = self.slot(slot)?;
+        if let Some(id) = self.slots[index] {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("slot {index} already contains contact {id}"),
+            ));
+        }
+
+        let id = self.next_tracking_id;
+        let first = self.active_touches() == 0;
+        let writer = self
+            .device
+            .writer()
+            .slot(slot)?
+            .set_tracking_id(id)?
+            .set_position(x, y)?
+            .finish_slot()?;
+        let writer = if first {
+            writer.write_events(&[
+                KeyEvent::new(Key::BTN_TOUCH, KeyState::PRESSED).into(),
+                KeyEvent::new(Key::BTN_TOOL_FINGER, KeyState::PRESSED).into(),
+            ])?
+        } else {
+            writer
+        };
+        writer.finish()?;
+
+        self.slots[index] = Some(id);
+        // Tracking IDs are non-negative; -1 is used to lift a contact.
+        self.next_tracking_id = id.checked_add(1).unwrap_or(0);
+        Ok(id)
+    }
+
+    /// Moves the contact in `slot` to position (`x`, `y`).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of type [`io::ErrorKind::InvalidInput`] if `slot` is out of range or
+    /// doesn't contain a contact.
+    pub fn touch_move(&mut self, slot: impl TryInto<Slot>, x: i32, y: i32) -> io::Result<()> {
+        let (slot, _) = self.active_slot(slot)?;
+        self.device
+            .writer()
+            .slot(slot)?
+            .set_position(x, y)?
+            .finish_slot()?
+            .finish()
+    }
+
+    /// Lifts the contact in `slot`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of type [`io::ErrorKind::InvalidInput`] if `slot` is out of range or
+    /// doesn't contain a contact.
+    pub fn touch_up(&mut self, slot: impl TryInto<Slot>) -> io::Result<()> {
+        let (slot, index) = self.active_slot(slot)?;
+        let last = self.active_touches() == 1;
+        let writer = self.device.writer().slot(slot)?.clear()?;
+        let writer = if last {
+            writer.write_events(&[
+                KeyEvent::new(Key::BTN_TOUCH, KeyState::RELEASED).into(),
+                KeyEvent::new(Key::BTN_TOOL_FINGER, KeyState::RELEASED).into(),
+            ])?
+        } else {
+            writer
+        };
+        writer.finish()?;
+
+        self.slots[index] = None;
+        Ok(())
+    }
+
+    fn slot(&self, slot: impl TryInto<Slot>) -> io::Result<(Slot, usize)> {
+        let slot: Slot = slot
+            .try_into()
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "invalid slot"))?;
+        let index = slot.raw() as usize;
+        if index >= self.slots.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "slot {index} out of range (device has {} slots)",
+                    self.slots.len()
+                ),
+            ));
+        }
+        Ok((slot, index))
+    }
+
+    fn active_slot(&self, slot: impl TryInto<Slot>) -> io::Result<(Slot, usize)> {
+        let (slot, index) = self.slot(slot)?;
+        if self.slots[index].is_none() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("slot {index} doesn't contain a contact"),
+            ));
+        }
+        Ok((slot, index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, io};
+
+    use crate::{
+        AbsInfo,
+        event::{Abs, Key},
+        test::pair,
+        uinput::AbsSetup,
+    };
+
+    use super::*;
+
+    #[test]
+    fn lifecycle() -> io::Result<()> {
+        if !fs::exists("/dev/uinput")? {
+            eprintln!("`/dev/uinput` doesn't exist, probably running under QEMU");
+            return Ok(());
+        }
+
+        let (uinput, evdev) = pair(|b| {
+            b.with_keys([Key::BTN_TOUCH, Key::BTN_TOOL_FINGER])?
+                .with_abs_axes([
+                    AbsSetup::new(Abs::MT_SLOT, AbsInfo::new(0, 1)),
+                    AbsSetup::new(Abs::MT_TRACKING_ID, AbsInfo::new(-1, i32::MAX)),
+                    AbsSetup::new(Abs::MT_POSITION_X, AbsInfo::new(0, 1000)),
+                    AbsSetup::new(Abs::MT_POSITION_Y, AbsInfo::new(0, 1000)),
+                ])
+        })?;
+        let mut reader = evdev.into_reader()?;
+        let mut touch = TouchEmulator::new(uinput, 2);
+
+        let a = touch.touch_down(0, 10, 20)?;
+        let b = touch.touch_down(1, 30, 40)?;
+        assert_ne!(a, b);
+        assert_eq!(touch.active_touches(), 2);
+        assert_eq!(
+            touch.touch_down(0, 0, 0).unwrap_err().kind(),
+            io::ErrorKind::InvalidInput
+        );
+        assert_eq!(
+            touch.touch_down(2, 0, 0).unwrap_err().kind(),
+            io::ErrorKind::InvalidInput
+        );
+
+        touch.touch_move(1, 50, 60)?;
+        reader.update()?;
+        assert!(reader.key_state().contains(Key::BTN_TOUCH));
+        assert_eq!(reader.slot_state(0, Abs::MT_TRACKING_ID), Some(a));
+        assert_eq!(reader.slot_state(1, Abs::MT_POSITION_X), Some(50));
+        assert_eq!(reader.slot_state(1, Abs::MT_POSITION_Y), Some(60));
+
+        touch.touch_up(0)?;
+        reader.update()?;
+        assert!(reader.key_state().contains(Key::BTN_TOUCH));
+        assert_eq!(reader.valid_slots().collect::<Vec<_>>(), &[1]);
+
+        touch.touch_up(1)?;
+        reader.update()?;
+        assert!(!reader.key_state().contains(Key::BTN_TOUCH));
+        assert!(!reader.key_state().contains(Key::BTN_TOOL_FINGER));
+        assert_eq!(reader.valid_slots().count(), 0);
+        assert_eq!(
+            touch.touch_move(1, 0, 0).unwrap_err().kind(),
+            io::ErrorKind::InvalidInput
+        );
+
+        Ok(())
+    }
+}
@@ -1,7 +1,9 @@
 use std::{
     ffi::c_int,
     io,
+    mem::MaybeUninit,
     os::fd::{AsRawFd, RawFd},
+    time::{Duration, SystemTime},
 };
 
 pub mod r#async;
@@ -40,6 +42,32 @@ pub fn block_until_readable(fd: RawFd) -> io::Result<()> {
     }
 }
 
+/// Uses `poll(2)` to wait up to `timeout` for `fd` to become readable.
+///
+/// Returns whether `fd` is readable.
+pub fn wait_readable(fd: RawFd, timeout: Duration) -> io::Result<bool> {
+    let mut poll = libc::pollfd {
+        fd: fd.as_raw_fd(),
+        events: libc::POLLIN,
+        revents: 0,
+    };
+    let ms = c_int::try_from(timeout.as_millis()).unwrap_or(c_int::MAX);
+    let ret = unsafe { libc::poll(&mut poll, 1, ms) };
+    if ret == -1 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(poll.revents & libc::POLLIN != 0)
+}
+
+pub fn is_nonblocking(fd: RawFd) -> io::Result<bool> {
+    let flags = unsafe { libc::fcntl(fd.as_raw_fd(), libc::F_GETFL) };
+    if flags == -1 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(flags & libc::O_NONBLOCK != 0)
+}
+
 pub fn set_nonblocking(fd: RawFd, nonblocking: bool) -> io::Result<bool> {
     let flags = unsafe { libc::fcntl(fd.as_raw_fd(), libc::F_GETFL) };
     if flags == -1 {
@@ -62,6 +90,52 @@ pub fn set_nonblocking(fd: RawFd, nonblocking: bool) -> io::Result<bool> {
     Ok(was_nonblocking)
 }
 
+pub fn set_cloexec(fd: RawFd, cloexec: bool) -> io::Result<bool> {
+    let flags = unsafe { libc::fcntl(fd.as_raw_fd(), libc::F_GETFD) };
+    if flags == -1 {
+        return Err(io::Error::last_os_error());
+    }
+
+    let was_cloexec = flags & libc::FD_CLOEXEC != 0;
+    let new_flags = if cloexec {
+        flags | libc::FD_CLOEXEC
+    } else {
+        flags & !libc::FD_CLOEXEC
+    };
+
+    if new_flags != flags {
+        let ret = unsafe { libc::fcntl(fd.as_raw_fd(), libc::F_SETFD, new_flags) };
+        if ret == -1 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+    Ok(was_cloexec)
+}
+
+/// Returns the access mode `fd` was opened with (one of `O_RDONLY`, `O_WRONLY`, or `O_RDWR`).
+pub fn access_mode(fd: RawFd) -> io::Result<c_int> {
+    let flags = unsafe { libc::fcntl(fd.as_raw_fd(), libc::F_GETFL) };
+    if flags == -1 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(flags & libc::O_ACCMODE)
+}
+
+/// Reads the current time of `clockid`, represented like event timestamps.
+pub fn clock_now(clockid: libc::clockid_t) -> io::Result<SystemTime> {
+    let mut ts = MaybeUninit::uninit();
+    let ts = unsafe {
+        if libc::clock_gettime(clockid, ts.as_mut_ptr()) == -1 {
+            return Err(io::Error::last_os_error());
+        }
+        ts.assume_init()
+    };
+
+    // `timespec` is normalized, and none of the clocks supported by evdev are negative.
+    let dur = Duration::new(ts.tv_sec as u64, ts.tv_nsec as u32);
+    Ok(SystemTime::UNIX_EPOCH + dur)
+}
+
 pub fn errorkind2libc(kind: io::ErrorKind) -> Option<c_int> {
     use io::ErrorKind::*;
 
@@ -51,7 +51,7 @@ impl Drop for AsyncHelper {
 }
 
 #[cfg(feature = "tokio")]
-use tokio_impl::*;
+pub use tokio_impl::*;
 #[cfg(feature = "tokio")]
 mod tokio_impl {
     use std::{io, os::fd::RawFd, task::Poll};
@@ -112,7 +112,7 @@ mod tokio_impl {
 }
 
 #[cfg(feature = "async-io")]
-use asyncio_impl::*;
+pub use asyncio_impl::*;
 #[cfg(feature = "async-io")]
 mod asyncio_impl {
     use std::{
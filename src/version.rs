@@ -6,9 +6,38 @@ use std::{ffi::c_int, fmt};
 /// driver.
 ///
 /// Returned by [`Evdev::driver_version`][crate::Evdev::driver_version].
-#[derive(Clone, Copy, PartialEq, Eq)]
+///
+/// Versions are ordered by their components, so they can be compared to check for a minimum
+/// version.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Version(pub(crate) c_int);
 
+impl Version {
+    /// Creates a [`Version`] from its components.
+    #[inline]
+    pub const fn new(major: u8, minor: u8, patch: u8) -> Self {
+        Self((major as c_int) << 16 | (minor as c_int) << 8 | patch as c_int)
+    }
+
+    /// Returns the major version.
+    #[inline]
+    pub const fn major(self) -> u8 {
+        (self.0 >> 16) as u8
+    }
+
+    /// Returns the minor version.
+    #[inline]
+    pub const fn minor(self) -> u8 {
+        (self.0 >> 8) as u8
+    }
+
+    /// Returns the patch version.
+    #[inline]
+    pub const fn patch(self) -> u8 {
+        self.0 as u8
+    }
+}
+
 impl fmt::Display for Version {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut first = true;
@@ -30,3 +59,19 @@ impl fmt::Debug for Version {
             .finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn components() {
+        let v = Version(0x010001);
+        assert_eq!(v, Version::new(1, 0, 1));
+        assert_eq!([v.major(), v.minor(), v.patch()], [1, 0, 1]);
+        assert_eq!(v.to_string(), "1.0.1");
+        assert!(Version::new(1, 0, 0) < v);
+        assert!(Version::new(0, 9, 9) < v);
+        assert!(Version::new(1, 1, 0) > v);
+    }
+}
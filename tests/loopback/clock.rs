@@ -1,4 +1,4 @@
-use std::io;
+use std::{io, time::Duration};
 
 use evdevil::event::{EventKind, Rel, RelEvent, Syn};
 
@@ -47,8 +47,18 @@ fn set_clockid() -> io::Result<()> {
         "{monotonic_time:?} <-> {wall_time:?}"
     );
 
+    // `clock_now` reads the clock selected via `set_clockid`, so the event's latency can be computed.
+    let latency = ev
+        .latency_from(t.evdev().clock_now()?)
+        .expect("event timestamp should be in the past");
+    assert!(latency < Duration::from_secs(10), "{latency:?}");
+
+    // `clock_now` tracks the selected clock.
+    assert!(t.evdev().clock_now()? < wall_time);
+
     // Restore default:
     t.evdev().set_clockid(libc::CLOCK_REALTIME)?;
+    assert!(t.evdev().clock_now()? >= wall_time);
 
     Ok(())
 }
@@ -1,6 +1,7 @@
 use std::{
     io,
     iter::zip,
+    task::{Context, Poll, Waker},
     thread,
     time::{Duration, SystemTime},
 };
@@ -12,6 +13,7 @@ use evdevil::{
         Abs, AbsEvent, EventKind, EventType, InputEvent, Key, KeyEvent, KeyState, Led, LedEvent,
         Rel, RelEvent, Syn, SynEvent,
     },
+    reader::Merge,
     uinput::UinputDevice,
 };
 
@@ -52,9 +54,12 @@ fn roundtrip_echo(t: &mut Tester, events: &[InputEvent]) -> io::Result<()> {
     roundtrip_raw(t, events)?;
 
     assert!(t.uinput.is_readable()?);
-    for expected in events {
-        let recv = t.uinput.events().next().unwrap()?;
-        if !events_eq(&recv, expected) {
+    let echoes = t.uinput.drain_echoes()?;
+    assert!(echoes.pending().is_none(), "unexpected event: {echoes:?}");
+    let echoes = echoes.echoes();
+    assert_eq!(echoes.len(), events.len(), "unexpected echoes: {echoes:?}");
+    for (recv, expected) in zip(echoes, events) {
+        if !events_eq(recv, expected) {
             panic!("expected {expected:?} in uinput device, got {recv:?}");
         }
     }
@@ -604,3 +609,276 @@ fn reader_events_stateless() -> io::Result<()> {
         Ok(())
     })
 }
+
+#[test]
+fn test_poll_next_report() -> io::Result<()> {
+    let mut t = Tester::get();
+    t.with_reader(|uinput, reader| {
+        let mut cx = Context::from_waker(Waker::noop());
+        reader.evdev().set_nonblocking(true)?;
+
+        assert!(reader.poll_next_report(&mut cx).is_pending());
+        assert!(reader.poll_next_event(&mut cx).is_pending());
+
+        uinput.write_events(&[RelEvent::new(Rel::DIAL, 1).into()])?;
+        let Poll::Ready(Some(report)) = reader.poll_next_report(&mut cx) else {
+            panic!("expected a report to be available");
+        };
+        check_events(
+            &report?.into_iter().collect::<Vec<_>>(),
+            &[RelEvent::new(Rel::DIAL, 1).into(), Syn::REPORT.into()],
+        );
+
+        uinput.write_events(&[RelEvent::new(Rel::DIAL, 2).into()])?;
+        let Poll::Ready(Some(event)) = reader.poll_next_event(&mut cx) else {
+            panic!("expected an event to be available");
+        };
+        check_events(&[event?], &[RelEvent::new(Rel::DIAL, 2).into()]);
+        let Poll::Ready(Some(event)) = reader.poll_next_event(&mut cx) else {
+            panic!("expected an event to be available");
+        };
+        check_events(&[event?], &[Syn::REPORT.into()]);
+        assert!(reader.poll_next_event(&mut cx).is_pending());
+
+        reader.evdev().set_nonblocking(false)?;
+        Ok(())
+    })
+}
+
+#[test]
+fn test_events_no_repeat() -> io::Result<()> {
+    const KEY: Key = Key::BTN_TRIGGER_HAPPY1;
+
+    let mut t = Tester::get();
+    t.with_reader(|uinput, reader| {
+        reader.evdev().set_nonblocking(true)?;
+
+        uinput.write_events(&[KeyEvent::new(KEY, KeyState::PRESSED).into()])?;
+        uinput.write_events(&[KeyEvent::new(KEY, KeyState::REPEAT).into()])?;
+        uinput.write_events(&[KeyEvent::new(KEY, KeyState::RELEASED).into()])?;
+
+        let events = reader.events_no_repeat().collect::<io::Result<Vec<_>>>()?;
+        check_events(
+            &events,
+            &[
+                KeyEvent::new(KEY, KeyState::PRESSED).into(),
+                Syn::REPORT.into(),
+                Syn::REPORT.into(),
+                KeyEvent::new(KEY, KeyState::RELEASED).into(),
+                Syn::REPORT.into(),
+            ],
+        );
+
+        reader.evdev().set_nonblocking(false)?;
+        Ok(())
+    })
+}
+
+#[test]
+fn test_tap_key() -> io::Result<()> {
+    const KEY: Key = Key::BTN_TRIGGER_HAPPY1;
+
+    let mut t = Tester::get();
+    t.with_reader(|uinput, reader| {
+        reader.evdev().set_nonblocking(true)?;
+
+        uinput.tap_key(KEY)?;
+        let events = reader.events().collect::<io::Result<Vec<_>>>()?;
+        check_events(
+            &events,
+            &[
+                KeyEvent::new(KEY, KeyState::PRESSED).into(),
+                Syn::REPORT.into(),
+                KeyEvent::new(KEY, KeyState::RELEASED).into(),
+                Syn::REPORT.into(),
+            ],
+        );
+
+        reader.evdev().set_nonblocking(false)?;
+        Ok(())
+    })
+}
+
+#[test]
+fn test_grab_and_reset() -> io::Result<()> {
+    let mut t = Tester::get();
+    t.with_reader(|uinput, reader| {
+        // This event is sent before the grab, so it must not be yielded afterwards.
+        uinput.write_events(&[RelEvent::new(Rel::DIAL, 1).into()])?;
+
+        let mut guard = reader.grab_and_reset()?;
+        guard.evdev().set_nonblocking(true)?;
+        assert!(guard.reports().next().is_none());
+
+        uinput.write_events(&[RelEvent::new(Rel::DIAL, 2).into()])?;
+        guard.evdev().set_nonblocking(false)?;
+        let report = guard.reports().next().unwrap()?;
+        check_events(
+            &report.into_iter().collect::<Vec<_>>(),
+            &[RelEvent::new(Rel::DIAL, 2).into(), Syn::REPORT.into()],
+        );
+
+        guard.ungrab()?;
+        Ok(())
+    })
+}
+
+#[test]
+fn test_merge() -> io::Result<()> {
+    let mut t = Tester::get();
+
+    // Every open file description receives its own copy of the events, so opening the device twice
+    // results in two readers that see the same reports.
+    let a = Evdev::open(&t.evdev_path)?.into_reader()?;
+    let b = Evdev::open(&t.evdev_path)?.into_reader()?;
+    let mut merge = Merge::new([a, b])?;
+    assert_eq!(merge.len(), 2);
+
+    merge.set_nonblocking(true);
+    assert!(merge.next().is_none());
+    merge.set_nonblocking(false);
+
+    roundtrip_raw(&mut t, &[RelEvent::new(Rel::DIAL, 1).into()])?;
+
+    let mut indices = Vec::new();
+    for _ in 0..2 {
+        let (index, report) = merge.next().unwrap()?;
+        check_events(
+            &report.into_iter().collect::<Vec<_>>(),
+            &[RelEvent::new(Rel::DIAL, 1).into(), Syn::REPORT.into()],
+        );
+        indices.push(index);
+    }
+    indices.sort();
+    assert_eq!(indices, [0, 1]);
+
+    merge.set_nonblocking(true);
+    assert!(merge.next().is_none());
+
+    let readers = merge.into_readers();
+    assert_eq!(readers.len(), 2);
+    for (_, reader) in &readers {
+        assert!(!reader.evdev().set_nonblocking(false)?);
+    }
+
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(target_os = "freebsd", ignore = "test broken on FreeBSD")]
+fn test_set_led_verified() -> io::Result<()> {
+    let t = Tester::get();
+
+    assert!(t.evdev().set_led_verified(Led::CAPSL, true)?);
+    // Already on, so the kernel ignores the event.
+    assert!(!t.evdev().set_led_verified(Led::CAPSL, true)?);
+    // The device doesn't support this LED.
+    assert!(!t.evdev().set_led_verified(Led::NUML, true)?);
+    assert!(t.evdev().set_led_verified(Led::CAPSL, false)?);
+
+    // Only the state changes are forwarded to the uinput device.
+    for on in [true, false] {
+        let recv = t.uinput.events().next().unwrap()?;
+        check_events(&[recv], &[LedEvent::new(Led::CAPSL, on).into()]);
+    }
+    while t.evdev().is_readable()? {
+        t.evdev().raw_events().next().unwrap()?;
+    }
+
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(target_os = "freebsd", ignore = "test broken on FreeBSD")]
+fn test_evdev_writer() -> io::Result<()> {
+    let t = Tester::get();
+
+    for on in [true, false] {
+        t.evdev()
+            .writer()
+            .write_events(&[LedEvent::new(Led::CAPSL, on).into()])?
+            .finish()?;
+        let expected = if on {
+            BitSet::from_iter([Led::CAPSL])
+        } else {
+            BitSet::new()
+        };
+        assert_eq!(t.evdev().led_state()?, expected);
+
+        let recv = t.uinput.events().next().unwrap()?;
+        check_events(&[recv], &[LedEvent::new(Led::CAPSL, on).into()]);
+    }
+
+    // The `SYN_REPORT` written by the `EventWriter` flushes the LED events to all evdev clients,
+    // including us.
+    while t.evdev().is_readable()? {
+        t.evdev().raw_events().next().unwrap()?;
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_write_iter() -> io::Result<()> {
+    let t = Tester::get();
+
+    t.evdev().write_iter(
+        [true, false]
+            .into_iter()
+            .flat_map(|on| [LedEvent::new(Led::CAPSL, on).into(), Syn::REPORT.into()]),
+    )?;
+    assert_eq!(t.evdev().led_state()?, BitSet::new());
+
+    let mut events = t.uinput.events();
+    check_events(
+        &[events.next().unwrap()?, events.next().unwrap()?],
+        &[
+            LedEvent::new(Led::CAPSL, true).into(),
+            LedEvent::new(Led::CAPSL, false).into(),
+        ],
+    );
+
+    while t.evdev().is_readable()? {
+        t.evdev().raw_events().next().unwrap()?;
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_with_reports() -> io::Result<()> {
+    let t = Tester::get();
+
+    t.uinput
+        .write_events(&[RelEvent::new(Rel::DIAL, 1).into()])?;
+    t.uinput.write_events(&[
+        RelEvent::new(Rel::DIAL, 2).into(),
+        RelEvent::new(Rel::DIAL, 3).into(),
+    ])?;
+
+    let mut reports = Vec::new();
+    let count = t
+        .evdev()
+        .with_reports(|report| reports.push(report.to_vec()))?;
+    assert_eq!(count, 2);
+    assert_eq!(reports.len(), 2);
+    check_events(
+        &reports[0],
+        &[RelEvent::new(Rel::DIAL, 1).into(), Syn::REPORT.into()],
+    );
+    check_events(
+        &reports[1],
+        &[
+            RelEvent::new(Rel::DIAL, 2).into(),
+            RelEvent::new(Rel::DIAL, 3).into(),
+            Syn::REPORT.into(),
+        ],
+    );
+
+    t.evdev().set_nonblocking(true)?;
+    let err = t.evdev().with_reports(|_| {}).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::WouldBlock);
+    t.evdev().set_nonblocking(false)?;
+
+    Ok(())
+}
@@ -1,13 +1,15 @@
 #![cfg(not(target_os = "freebsd"))] // FreeBSD does not support force-feedback (stubbed out)
 
-use std::{collections::HashSet, error::Error, io, sync::mpsc};
+use std::{collections::HashSet, error::Error, io, sync::mpsc, thread, time::Duration};
 
 use evdevil::{
+    Evdev,
     event::{EventKind, ForceFeedbackCode, Rel, RelEvent, UinputCode},
-    ff::{Effect, EffectId, Rumble},
+    ff::{Effect, EffectId, Feature, Rumble},
+    uinput::UinputDevice,
 };
 
-use crate::Tester;
+use crate::{FF_EFFECTS, Tester};
 
 struct FFTest<'a> {
     t: &'a mut Tester,
@@ -50,6 +52,7 @@ impl<'a> FFTest<'a> {
             EventKind::Uinput(ui) if ui.code() == UinputCode::FF_UPLOAD => {
                 log::debug!("got event {ui:?}");
                 self.t.uinput.ff_upload(&ui, |upl| {
+                    assert_eq!(upl.request_id(), ui.raw_value() as u32);
                     assert_eq!(upl.effect().effect_type(), effect.effect_type());
                     assert_eq!(upl.effect().kind(), effect.kind());
                     res.map(|()| upl.effect_id())
@@ -121,6 +124,7 @@ impl<'a> FFTest<'a> {
         match self.t.uinput.events().next().unwrap()?.kind() {
             EventKind::Uinput(ui) if ui.code() == UinputCode::FF_ERASE => {
                 self.t.uinput.ff_erase(&ui, |erase| {
+                    assert_eq!(erase.request_id(), ui.raw_value() as u32);
                     assert_eq!(erase.effect_id(), id);
                     Ok(())
                 })?;
@@ -185,6 +189,119 @@ fn upload_play_remove() -> io::Result<()> {
     Ok(())
 }
 
+#[test]
+fn rumble() -> io::Result<()> {
+    let mut t = Tester::get();
+    let mut t = FFTest::new(&mut t);
+
+    let (send, recv) = mpsc::sync_channel(1);
+    t.t.with_evdev_thread(move |evdev| {
+        let res = evdev.rumble(10, 100, Duration::from_millis(250));
+        send.send(res).unwrap();
+        Ok(())
+    });
+
+    let id = match t.t.uinput.events().next().unwrap()?.kind() {
+        EventKind::Uinput(ui) if ui.code() == UinputCode::FF_UPLOAD => {
+            t.t.uinput.ff_upload(&ui, |upl| {
+                assert_eq!(upl.effect().kind(), Effect::from(EFFECT).kind());
+                assert_eq!(upl.effect().replay().length(), 250);
+                Ok(upl.effect_id())
+            })?
+        }
+        e => panic!("unexpected event: {e:?}"),
+    };
+    match t.t.uinput.events().next().unwrap()?.kind() {
+        EventKind::ForceFeedback(ev) => {
+            assert_eq!(ev.code(), ForceFeedbackCode::ControlEffect(id));
+            assert_eq!(ev.raw_value(), 1);
+        }
+        e => panic!("unexpected event: {e:?}"),
+    }
+    t.t.join_thread();
+    assert_eq!(recv.recv().unwrap()?, id);
+    t.uploaded.insert(id);
+    t.playing.insert(id);
+
+    t.erase_effect(id)?;
+
+    let err =
+        t.t.evdev()
+            .rumble(0, 0, Duration::from_secs(100))
+            .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    Ok(())
+}
+
+#[test]
+fn update_effect() -> io::Result<()> {
+    let mut t = Tester::get();
+    let mut t = FFTest::new(&mut t);
+
+    let id = t.upload_effect(EFFECT)?;
+
+    let updated = Rumble::new(1000, 2000);
+    t.t.with_evdev_thread(move |evdev| evdev.update_ff_effect(id, updated));
+    match t.t.uinput.events().next().unwrap()?.kind() {
+        EventKind::Uinput(ui) if ui.code() == UinputCode::FF_UPLOAD => {
+            t.t.uinput.ff_upload(&ui, |upl| {
+                assert_eq!(upl.effect_id(), id);
+                assert_eq!(upl.effect().kind(), Effect::from(updated).kind());
+                assert_eq!(upl.old().kind(), Effect::from(EFFECT).kind());
+                Ok(())
+            })?;
+        }
+        e => panic!("unexpected event: {e:?}"),
+    }
+    t.t.join_thread();
+
+    t.erase_effect(id)?;
+    Ok(())
+}
+
+#[test]
+fn effect_handle() -> io::Result<()> {
+    let mut t = Tester::get();
+    let t = FFTest::new(&mut t);
+
+    let (send, recv) = mpsc::sync_channel(1);
+    t.t.with_evdev_thread(move |evdev| {
+        let handle = evdev.upload_ff_effect_guard(EFFECT)?;
+        send.send(handle.id()).unwrap();
+        drop(handle);
+        Ok(())
+    });
+
+    match t.t.uinput.events().next().unwrap()?.kind() {
+        EventKind::Uinput(ui) if ui.code() == UinputCode::FF_UPLOAD => {
+            t.t.uinput.ff_upload(&ui, |upl| Ok(upl.effect_id()))?;
+        }
+        e => panic!("unexpected event: {e:?}"),
+    }
+    let id = recv.recv().unwrap();
+
+    // Dropping the handle erases the effect, which stops it first.
+    match t.t.uinput.events().next().unwrap()?.kind() {
+        EventKind::ForceFeedback(ev) => {
+            assert_eq!(ev.code(), ForceFeedbackCode::ControlEffect(id));
+            assert_eq!(ev.raw_value(), 0);
+        }
+        e => panic!("unexpected event: {e:?}"),
+    }
+    match t.t.uinput.events().next().unwrap()?.kind() {
+        EventKind::Uinput(ui) if ui.code() == UinputCode::FF_ERASE => {
+            t.t.uinput.ff_erase(&ui, |erase| {
+                assert_eq!(erase.effect_id(), id);
+                Ok(())
+            })?;
+        }
+        e => panic!("unexpected event: {e:?}"),
+    }
+    t.t.join_thread();
+
+    Ok(())
+}
+
 #[test]
 fn upload_too_many() -> io::Result<()> {
     // Device is created with support for 2 effects.
@@ -237,3 +354,116 @@ fn upload_error() -> io::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn upload_timeout() -> io::Result<()> {
+    let mut t = Tester::get();
+    let t = FFTest::new(&mut t);
+
+    let (send, recv) = mpsc::sync_channel(1);
+    t.t.with_evdev_thread(move |evdev| {
+        send.send(evdev.upload_ff_effect(EFFECT)).unwrap();
+        Ok(())
+    });
+    match t.t.uinput.events().next().unwrap()?.kind() {
+        EventKind::Uinput(ui) if ui.code() == UinputCode::FF_UPLOAD => {
+            let err =
+                t.t.uinput
+                    .ff_upload_timeout(&ui, Duration::ZERO, |_| -> io::Result<()> {
+                        panic!("handler should not be invoked for an expired request")
+                    })
+                    .unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+        }
+        e => panic!("unexpected event: {e:?}"),
+    }
+    t.t.join_thread();
+
+    let err = recv.recv().unwrap().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+    Ok(())
+}
+
+#[test]
+fn upload_timeout_late() -> io::Result<()> {
+    let mut t = Tester::get();
+    let t = FFTest::new(&mut t);
+
+    let (send, recv) = mpsc::sync_channel(1);
+    t.t.with_evdev_thread(move |evdev| {
+        send.send(evdev.upload_ff_effect(EFFECT)).unwrap();
+        Ok(())
+    });
+    match t.t.uinput.events().next().unwrap()?.kind() {
+        EventKind::Uinput(ui) if ui.code() == UinputCode::FF_UPLOAD => {
+            let err =
+                t.t.uinput
+                    .ff_upload_timeout(&ui, Duration::from_millis(10), |_| {
+                        thread::sleep(Duration::from_millis(50));
+                        Ok(123)
+                    })
+                    .unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+            // The handler's result is handed back so that it can be cleaned up.
+            assert_eq!(err.into_late_result(), Some(123));
+        }
+        e => panic!("unexpected event: {e:?}"),
+    }
+    t.t.join_thread();
+
+    let err = recv.recv().unwrap().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+    Ok(())
+}
+
+#[test]
+fn try_set_unsupported() -> io::Result<()> {
+    let t = Tester::get();
+    // The test device only supports `FF_RUMBLE`.
+    let err = t.evdev().try_set_ff_gain(0x8000).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+    let err = t.evdev().try_set_ff_autocenter(0x8000).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+    Ok(())
+}
+
+#[test]
+fn ff_settings_tracked() -> io::Result<()> {
+    let t = Tester::get();
+    // The device doesn't support `FF_GAIN`, so the kernel drops the event, but the setting is
+    // still tracked by the handle.
+    t.evdev().set_ff_gain(0x4000)?;
+    assert_eq!(t.evdev().ff_gain(), Some(0x4000));
+    let clone = t.evdev().try_clone()?;
+    assert_eq!(clone.ff_gain(), Some(0x4000));
+
+    // Clones refer to the same open file, so they share the settings.
+    clone.set_ff_gain(0x2000)?;
+    assert_eq!(t.evdev().ff_gain(), Some(0x2000));
+
+    let evdev = Evdev::open(&t.evdev_path)?;
+    assert_eq!(evdev.ff_gain(), None);
+    assert_eq!(evdev.ff_autocenter(), None);
+    Ok(())
+}
+
+#[test]
+fn supports_ff_requests() {
+    let t = Tester::get();
+    assert!(t.uinput.supports_ff_requests());
+}
+
+#[test]
+fn ff_effects_max() -> io::Result<()> {
+    let t = Tester::get();
+    assert_eq!(t.uinput.ff_effects_max(), FF_EFFECTS);
+    assert_eq!(t.uinput.try_clone()?.ff_effects_max(), FF_EFFECTS);
+
+    // Enabling force-feedback features without a maximum effect count is rejected.
+    let err = UinputDevice::builder()?
+        .with_ff_features([Feature::RUMBLE])?
+        .build("ff without effects")
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    Ok(())
+}
@@ -30,9 +30,9 @@ use std::{
 };
 
 use evdevil::{
-    AbsInfo, Bus, Evdev, EventReader, InputId, InputProp, KeyRepeat,
+    AbsInfo, AccessMode, Bus, Evdev, EventReader, InputId, InputProp, KeyRepeat,
     bits::{BitSet, BitValue},
-    event::{Abs, EventType, Key, Led, Misc, Rel, Sound, Switch},
+    event::{Abs, EventType, Key, Led, Misc, Rel, RelEvent, Sound, Switch},
     ff::Feature,
     uinput::{AbsSetup, UinputDevice},
 };
@@ -145,7 +145,9 @@ impl Tester {
                     // (on FreeBSD this fails with EINVAL)
                     assert_eq!(self.uinput.set_nonblocking(false).unwrap(), false);
                 }
-                // NB: does not check multitouch slot states, due to lack of evdevil API
+                let mt_state = self.evdev().mt_slot_state().unwrap();
+                assert_eq!(mt_state.slots().count(), 0, "{mt_state:?}");
+                assert_eq!(mt_state.current_slot(), 0);
             }
         }
         impl Deref for TesterHandle {
@@ -302,8 +304,9 @@ fn setup_uinput_device() -> io::Result<UinputDevice> {
     dev.writer().set_key_repeat(KEY_REPEAT)?.finish()?;
     // The events are echoed right back at us, but not on FreeBSD.
     if !cfg!(target_os = "freebsd") {
-        dev.events().next().unwrap()?;
-        dev.events().next().unwrap()?;
+        let echoes = dev.drain_echoes()?;
+        assert_eq!(echoes.echoes().len(), 2, "unexpected echoes: {echoes:?}");
+        assert!(echoes.pending().is_none(), "unexpected event: {echoes:?}");
     }
 
     Ok(dev)
@@ -317,6 +320,13 @@ fn test_device_id() -> io::Result<()> {
     assert_eq!(devid.vendor(), INPUT_ID.vendor());
     assert_eq!(devid.product(), INPUT_ID.product());
     assert_eq!(devid.version(), INPUT_ID.version());
+
+    // The second call is served from the cache.
+    assert_eq!(tester.evdev().input_id()?, devid);
+    assert_eq!(
+        tester.evdev().vendor_product_string()?,
+        format!("{:04x}:{:04x}", INPUT_ID.vendor(), INPUT_ID.product()),
+    );
     Ok(())
 }
 
@@ -350,6 +360,32 @@ fn test_uniq() -> io::Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_backend_caps() -> io::Result<()> {
+    let t = Tester::get();
+    let caps = t.evdev().backend_caps();
+    assert_eq!(
+        caps.syn_in_supported_events(),
+        t.evdev().supported_events()?.contains(EventType::SYN),
+    );
+    assert_eq!(caps.event_masks(), t.evdev().event_mask().is_ok());
+    assert_eq!(caps.force_feedback(), t.uinput.supports_ff_requests());
+    Ok(())
+}
+
+#[test]
+fn test_metadata() -> io::Result<()> {
+    let t = Tester::get();
+    let evdev = t.evdev();
+    let meta = evdev.metadata()?;
+    assert_eq!(meta.input_id(), INPUT_ID);
+    assert_eq!(meta.name(), TEST_DEVICE_NAME);
+    assert_eq!(meta.phys(), Some(PHYS));
+    assert_eq!(meta.unique_id(), None);
+    assert_eq!(meta.driver_version(), evdev.driver_version()?);
+    Ok(())
+}
+
 #[test]
 fn test_sysname() -> io::Result<()> {
     let t = Tester::get();
@@ -402,8 +438,19 @@ fn test_advertised_event_codes() -> io::Result<()> {
             EventType::FF,
         ])
     );
+    assert!(t.evdev().has_event_type(EventType::SYN)?);
+    assert!(t.evdev().has_event_type(EventType::FF)?);
+    assert!(!t.evdev().has_event_type(EventType::PWR)?);
+    assert_eq!(
+        t.evdev().key_capabilities()?,
+        Some(t.evdev().supported_keys()?)
+    );
     check(t.evdev().supported_keys()?, KEYS);
     check(t.evdev().supported_rel_axes()?, REL);
+    assert!(t.evdev().has_rel_axis(Rel::DIAL)?);
+    assert!(!t.evdev().has_rel_axis(Rel::X)?);
+    let caps = t.evdev().pointer_caps()?;
+    assert!(!caps.has_x() && !caps.has_wheel() && !caps.has_hi_res());
     check(t.evdev().supported_misc()?, MISC);
     check(t.evdev().supported_leds()?, LEDS);
     check(t.evdev().supported_sounds()?, SOUNDS);
@@ -416,6 +463,7 @@ fn test_advertised_event_codes() -> io::Result<()> {
         .into_iter()
         .collect::<Vec<_>>();
     assert_eq!(expected, actual);
+    check(t.evdev().supported_plain_abs_axes()?, &[Abs::BRAKE]);
 
     if !cfg!(target_os = "freebsd") {
         assert_eq!(t.evdev().supported_ff_effects()?, FF_EFFECTS);
@@ -448,3 +496,249 @@ fn test_abs_info() -> io::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_apply_abs_profile() -> io::Result<()> {
+    let t = Tester::get();
+
+    let new_info = AbsInfo::new(
+        ABS_INFO_BRAKE.minimum() + 100,
+        ABS_INFO_BRAKE.maximum() - 100,
+    );
+    t.evdev()
+        .apply_abs_profile([AbsSetup::new(Abs::BRAKE, new_info)])?;
+    assert_eq!(t.evdev().abs_info(Abs::BRAKE)?, new_info);
+
+    let err = t
+        .evdev()
+        .apply_abs_profile([
+            AbsSetup::new(Abs::BRAKE, ABS_INFO_BRAKE),
+            AbsSetup::new(Abs::from_raw(Abs::MAX.raw() + 1), ABS_INFO_BRAKE),
+        ])
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    assert!(err.to_string().contains("failed to apply profile"), "{err}");
+    assert_eq!(t.evdev().abs_info(Abs::BRAKE)?, ABS_INFO_BRAKE);
+
+    Ok(())
+}
+
+#[test]
+fn test_try_set_abs_info() -> io::Result<()> {
+    let t = Tester::get();
+
+    let inverted = AbsInfo::new(100, -100);
+    let err = t
+        .evdev()
+        .try_set_abs_info(Abs::BRAKE, inverted)
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    assert_eq!(t.evdev().abs_info(Abs::BRAKE)?, ABS_INFO_BRAKE);
+
+    let err = t
+        .evdev()
+        .try_set_abs_info(Abs::MT_SLOT, ABS_INFO_MT_SLOTS)
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+    t.evdev().try_set_abs_info(Abs::BRAKE, ABS_INFO_BRAKE)?;
+    assert_eq!(t.evdev().abs_info(Abs::BRAKE)?, ABS_INFO_BRAKE);
+
+    Ok(())
+}
+
+#[test]
+fn test_nonblocking_scope() -> io::Result<()> {
+    let t = Tester::get();
+
+    {
+        let guard = t.evdev().nonblocking_scope()?;
+        assert!(!guard.was_nonblocking());
+        // No events are pending, so this must not block.
+        assert!(guard.raw_events().next().is_none());
+    }
+    // Dropping the guard moves the device back into blocking mode.
+    assert!(!t.evdev().set_nonblocking(false)?);
+
+    t.evdev().set_nonblocking(true)?;
+    {
+        let guard = t.evdev().nonblocking_scope()?;
+        assert!(guard.was_nonblocking());
+    }
+    assert!(t.evdev().set_nonblocking(false)?);
+
+    Ok(())
+}
+
+#[test]
+fn test_enumerate_in() -> io::Result<()> {
+    let t = Tester::get();
+
+    let found = evdevil::enumerate::enumerate_in("/dev/input")?
+        .filter_map(Result::ok)
+        .any(|(path, _)| path == t.evdev_path);
+    assert!(found, "test device not found by `enumerate_in`");
+
+    let err = evdevil::enumerate::enumerate_in("/this/path/does/not/exist").unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::NotFound);
+
+    let err = Evdev::open("/dev/null").unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+    // Character devices outside of `/dev/input` are rejected without being opened unless they are
+    // evdev nodes, even if they are named `event*`.
+    let dir = std::env::temp_dir().join(format!("evdevil-enumerate-in-{}", std::process::id()));
+    std::fs::create_dir_all(&dir)?;
+    let _ = std::fs::remove_file(dir.join("event0"));
+    std::os::unix::fs::symlink("/dev/null", dir.join("event0"))?;
+    let err = Evdev::open(dir.join("event0")).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    let results = evdevil::enumerate::enumerate_in(&dir)?.collect::<Vec<_>>();
+    std::fs::remove_dir_all(&dir)?;
+    assert_eq!(results.len(), 1);
+    assert_eq!(
+        results[0].as_ref().unwrap_err().kind(),
+        io::ErrorKind::InvalidInput
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_uinput_builder_at() -> io::Result<()> {
+    UinputDevice::builder_at("/dev/uinput")?;
+
+    // `/dev/null` doesn't understand `uinput` ioctls.
+    UinputDevice::builder_at("/dev/null").unwrap_err();
+
+    Ok(())
+}
+
+#[test]
+fn test_open_reporting() -> io::Result<()> {
+    let t = Tester::get();
+
+    let (evdev, mode) = Evdev::open_reporting(&t.evdev_path)?;
+    assert_eq!(mode, evdev.access_mode()?);
+    assert_eq!(mode == AccessMode::ReadOnly, !evdev.can_write()?);
+    assert_eq!(mode == AccessMode::WriteOnly, !evdev.can_read()?);
+
+    Ok(())
+}
+
+#[test]
+fn test_is_nonblocking() -> io::Result<()> {
+    let t = Tester::get();
+
+    assert!(!t.evdev().is_nonblocking()?);
+    {
+        let _guard = t.evdev().nonblocking_scope()?;
+        assert!(t.evdev().is_nonblocking()?);
+        assert!(t.evdev().try_clone()?.is_nonblocking()?);
+    }
+    assert!(!t.evdev().is_nonblocking()?);
+
+    Ok(())
+}
+
+#[test]
+fn test_reopen() -> io::Result<()> {
+    let t = Tester::get();
+
+    let reopened = t.evdev().reopen()?;
+    assert_eq!(reopened.id()?, t.evdev().id()?);
+
+    // The blocking mode is not shared with the original handle.
+    assert!(!reopened.set_nonblocking(true)?);
+    assert!(!t.evdev().set_nonblocking(false)?);
+
+    t.uinput
+        .write_events(&[RelEvent::new(Rel::DIAL, 1).into()])?;
+    assert!(reopened.is_readable()?);
+    while t.evdev().is_readable()? {
+        t.evdev().raw_events().next().unwrap()?;
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_by_id_path() -> io::Result<()> {
+    let t = Tester::get();
+
+    // Virtual devices usually don't get a `by-id` link, but if there is one, it has to point to
+    // our device.
+    if let Some(path) = t.evdev().by_id_path()? {
+        assert_eq!(fs::canonicalize(path)?, fs::canonicalize(&t.evdev_path)?);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_device_key() -> io::Result<()> {
+    let t = Tester::get();
+
+    let key = t.evdev().id()?;
+    assert_eq!(t.evdev().try_clone()?.id()?, key);
+    assert_eq!(Evdev::open(&t.evdev_path)?.id()?, key);
+
+    let other = evdevil::enumerate()?
+        .filter_map(Result::ok)
+        .find(|(path, _)| *path != t.evdev_path);
+    if let Some((_, other)) = other {
+        assert_ne!(other.id()?, key);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_open_with_retry() -> io::Result<()> {
+    let t = Tester::get();
+
+    let evdev = Evdev::open_with_retry(&t.evdev_path, 3, Duration::from_millis(1))?;
+    assert_eq!(evdev.id()?, t.evdev().id()?);
+
+    let err = Evdev::open_with_retry("/dev/input/event-nonexistent", 3, Duration::from_millis(1))
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::NotFound);
+
+    Ok(())
+}
+
+#[test]
+fn test_cloexec() -> io::Result<()> {
+    let t = Tester::get();
+    let evdev = t.evdev();
+    assert!(
+        evdev.set_cloexec(false)?,
+        "`Evdev::open` should set `FD_CLOEXEC`"
+    );
+    assert!(!evdev.set_cloexec(true)?);
+    assert!(evdev.set_cloexec(true)?);
+
+    assert!(
+        t.uinput.set_cloexec(false)?,
+        "`UinputDevice::builder` should set `FD_CLOEXEC`"
+    );
+    assert!(!t.uinput.set_cloexec(true)?);
+
+    Ok(())
+}
+
+#[test]
+fn test_access_mode() -> io::Result<()> {
+    let t = Tester::get();
+    assert!(t.evdev().can_read()?);
+    assert!(t.evdev().can_write()?);
+
+    let file = fs::File::options().write(true).open(&t.evdev_path)?;
+    let evdev = unsafe { Evdev::from_owned_fd(file.into()) };
+    assert!(!evdev.can_read()?);
+    assert!(evdev.can_write()?);
+    let err = evdev.into_reader().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::PermissionDenied);
+
+    Ok(())
+}
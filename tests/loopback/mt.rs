@@ -36,12 +36,15 @@ fn smoke() -> io::Result<()> {
         assert_eq!(reader.slot_state(3, Abs::MT_POSITION_X), Some(900));
         assert_eq!(reader.slot_state(3, Abs::MT_POSITION_Y), Some(999));
 
-        uinput
-            .writer()
-            .slot(0)?
-            .set_tracking_id(-1)?
-            .finish_slot()?
-            .finish()?;
+        let snapshot = reader.evdev().mt_slot_state()?;
+        assert_eq!(snapshot, reader.slot_snapshot());
+
+        // Re-fetching the state from the device shouldn't change anything.
+        reader.invalidate_slots()?;
+        reader.update()?;
+        assert_eq!(reader.slot_snapshot(), snapshot);
+
+        uinput.writer().slot(0)?.clear()?.finish()?;
 
         reader.update()?;
 
@@ -71,3 +74,44 @@ fn smoke() -> io::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn slots() -> io::Result<()> {
+    let mut t = Tester::get();
+
+    t.with_reader(|uinput, reader| {
+        let touches = [(1, 10, 20), (2, 30, 40)];
+        uinput
+            .writer()
+            .slots(touches.map(|t| (t.0, t)), |w, (id, x, y)| {
+                w.set_tracking_id(id)?.set_position(x, y)
+            })?
+            .finish()?;
+
+        reader.update()?;
+
+        let slots = reader.valid_slots().collect::<Vec<_>>();
+        assert_eq!(slots, &[1, 2]);
+        assert_eq!(reader.slot_state(1, Abs::MT_POSITION_X), Some(10));
+        assert_eq!(reader.slot_state(2, Abs::MT_POSITION_Y), Some(40));
+
+        // Reset slot state, so that later tests start out clean.
+        uinput
+            .writer()
+            .slots([1, 2].map(|s| (s, ())), |w, ()| {
+                w.set_position(0, 0)?.set_tracking_id(-1)
+            })?
+            .slot(0)?
+            .finish_slot()?
+            .finish()?;
+
+        reader.update()?;
+
+        let slots = reader.valid_slots().collect::<Vec<_>>();
+        assert_eq!(slots, &[] as &[i32]);
+
+        Ok(())
+    })?;
+
+    Ok(())
+}
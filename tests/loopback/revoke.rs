@@ -15,7 +15,11 @@ fn revoke() -> io::Result<()> {
     assert!(!dev2.is_readable()?);
 
     // After revocation, dev2 shouldn't receive any events anymore
+    assert!(!dev2.is_revoked());
     dev2.revoke()?;
+    assert!(dev2.is_revoked());
+    assert!(dev2.try_clone()?.is_revoked());
+    assert!(!dev.is_revoked());
     t.uinput.write_events(&[RelEvent::new(Rel::DIAL, 1).into()])?;
 
     assert!(dev.is_readable()?);
@@ -24,6 +28,7 @@ fn revoke() -> io::Result<()> {
     // Further uses of `dev2` (via `write` or `ioctl`) result in `ENODEV`.
     match dev2.revoke() {
         Err(e) => {
+            assert_eq!(e.kind(), io::ErrorKind::PermissionDenied);
             let mut e: &dyn Error = &e;
             while let Some(s) = e.source() {
                 e = s;